@@ -7,6 +7,8 @@ pub enum Action {
     Call,
     Bet(Bet),
     Raise(Bet),
+    // Puts in the rest of the player's stack
+    AllIn,
     None,
 }
 
@@ -18,6 +20,7 @@ impl Action {
             Action::Bet(bet) => format!("B{}", bet),
             Action::Raise(bet) => format!("R{}", bet),
             Action::Check => "X".to_string(),
+            Action::AllIn => "A".to_string(),
             Action::None => "-".to_string(),
         }
     }
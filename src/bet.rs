@@ -4,6 +4,10 @@ use std::fmt;
 pub enum Bet {
     C(u32),
     P(u32),
+    // Geometric size that gets the stacks in by the final street
+    G,
+    // Raise to a multiple of the previous bet, in percent
+    X(u32),
 }
 
 impl fmt::Display for Bet {
@@ -11,6 +15,8 @@ impl fmt::Display for Bet {
         match self {
             Bet::C(chip) => write!(f, "{}c", chip),
             Bet::P(percentage) => write!(f, "{:.2}", percentage),
+            Bet::G => write!(f, "g"),
+            Bet::X(multiple) => write!(f, "{}x", multiple),
         }
     }
 }
\ No newline at end of file
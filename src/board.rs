@@ -1,4 +1,6 @@
 use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::suit::Suit;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
 pub struct Board {
@@ -18,6 +20,15 @@ impl Board {
         }
     }
 
+    // Cards written one after another, such as AdJc2h
+    pub fn new_from_string(board_str: &str) -> Self {
+        let chars: Vec<char> = board_str.chars().collect();
+        assert!(chars.len().is_multiple_of(2), "Invalid board string: {}", board_str);
+        Board::from_vec(chars.chunks(2)
+            .map(|card| Card::new(Card::rank_from_char(card[0]), Suit::from_char(card[1])))
+            .collect())
+    }
+
     pub fn push(&mut self, card: Card) {
         self.cards.push(card);
     }
@@ -29,4 +40,35 @@ impl Board {
     pub fn to_vec(&self) -> Vec<Card> {
         self.cards.clone()
     }
+
+    // Exchanging suits a and b maps the board onto itself
+    pub fn is_suit_symmetric(&self, a: Suit, b: Suit) -> bool {
+        let cards = CardSet::from(self);
+        cards.swap_suits(a, b) == cards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_from_string() {
+        let board = Board::new_from_string("AdJc2h");
+        assert_eq!(board.cards, vec![Card::new(14, Suit::Diamonds), Card::new(11, Suit::Clubs), Card::new(2, Suit::Hearts)]);
+    }
+
+    #[test]
+    fn test_is_suit_symmetric() {
+        let board = Board::from_vec(vec![
+            Card::new(14, Suit::Spades),
+            Card::new(14, Suit::Hearts),
+            Card::new(7, Suit::Diamonds),
+        ]);
+
+        assert!(board.is_suit_symmetric(Suit::Spades, Suit::Hearts));
+        assert!(!board.is_suit_symmetric(Suit::Spades, Suit::Diamonds));
+        assert!(!board.is_suit_symmetric(Suit::Diamonds, Suit::Clubs));
+        assert!(board.is_suit_symmetric(Suit::Clubs, Suit::Clubs));
+    }
 }
\ No newline at end of file
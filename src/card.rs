@@ -18,6 +18,30 @@ impl Card {
         Card { rank, suit: Suit::Diamonds }
     }
 
+    // Cards of rank 2 to ace in one of the four suits have an index 0-51,
+    // 13 ranks per suit. The lowest Kuhn and Leduc card, rank 1, and meta
+    // suits have none.
+    pub fn has_index(&self) -> bool {
+        (2..15).contains(&self.rank) && self.suit.to_usize() < 4
+    }
+
+    pub fn to_index(&self) -> u8 {
+        assert!(self.has_index(), "No index for card {}", self);
+        self.suit.to_usize() as u8 * 13 + self.rank - 2
+    }
+
+    pub fn from_index(index: u8) -> Card {
+        assert!(index < 52, "Invalid card index {}", index);
+        let suit = Suit::to_vec().into_iter().find(|suit| suit.to_usize() as u8 == index / 13).unwrap();
+        Card::new(index % 13 + 2, suit)
+    }
+
+    // The card with suits a and b exchanged
+    pub fn swap_suits(&self, a: Suit, b: Suit) -> Card {
+        let suit = if self.suit == a { b } else if self.suit == b { a } else { self.suit };
+        Card { rank: self.rank, suit }
+    }
+
     pub fn rank_from_char(c: char) -> u8 {
         match c {
             'A' => 14,
@@ -29,6 +53,18 @@ impl Card {
             _ => panic!("Invalid rank character: {}", c),
         }
     }
+
+    pub fn rank_to_char(rank: u8) -> char {
+        match rank {
+            14 => 'A',
+            13 => 'K',
+            12 => 'Q',
+            11 => 'J',
+            10 => 'T',
+            2..=9 => char::from_digit(rank as u32, 10).unwrap(),
+            _ => panic!("No character for rank {}", rank),
+        }
+    }
 }
 
 impl fmt::Display for Card {
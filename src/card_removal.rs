@@ -0,0 +1,108 @@
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
+use crate::player::Player;
+use std::collections::HashMap;
+
+// Cards of every hand of both players. Used to exclude opponent hands that
+// share a card with the player's hand when summing over a range.
+pub struct CardRemoval {
+    // Card ids of each hand, by Player::as_usize. Kuhn style hands repeat
+    // a single card.
+    hand_cards: [Vec<[usize; 2]>; 2],
+    // Index of the same hand among the opponent's hands
+    same_hand: [Vec<Option<usize>>; 2],
+    card_ids: HashMap<Card, usize>,
+}
+
+impl CardRemoval {
+    pub fn new(hands: &[Vec<HoleCards>; 2]) -> Self {
+        let mut card_ids = HashMap::new();
+        for hand in hands.iter().flatten() {
+            for card in hand.cards() {
+                let id = card_ids.len();
+                card_ids.entry(card).or_insert(id);
+            }
+        }
+
+        let hand_cards = [0, 1].map(|p| hands[p].iter()
+            .map(|hand| [card_ids[&hand.card1], card_ids[&hand.card2]])
+            .collect());
+        let same_hand = [0, 1].map(|p| hands[p].iter()
+            .map(|hand| hands[1 - p].iter().position(|other| other == hand))
+            .collect());
+
+        CardRemoval {
+            hand_cards,
+            same_hand,
+            card_ids,
+        }
+    }
+
+    pub fn card_count(&self) -> usize {
+        self.card_ids.len()
+    }
+
+    pub fn contains(&self, player: Player, hand: usize, card: &Card) -> bool {
+        match self.card_ids.get(card) {
+            Some(id) => self.hand_cards[player.as_usize()][hand].contains(id),
+            None => false,
+        }
+    }
+
+    // Total reach of the player's hands and the reach of the hands holding each card
+    pub fn card_sums(&self, player: Player, reach: &[f64]) -> (f64, Vec<f64>) {
+        let mut card_sums = vec![0.0; self.card_count()];
+        for (cards, r) in self.hand_cards[player.as_usize()].iter().zip(reach.iter()) {
+            Self::add(&mut card_sums, cards, *r);
+        }
+
+        (reach.iter().sum(), card_sums)
+    }
+
+    pub fn add(card_sums: &mut [f64], cards: &[usize; 2], reach: f64) {
+        card_sums[cards[0]] += reach;
+        if cards[1] != cards[0] {
+            card_sums[cards[1]] += reach;
+        }
+    }
+
+    pub fn hand_cards(&self, player: Player, hand: usize) -> &[usize; 2] {
+        &self.hand_cards[player.as_usize()][hand]
+    }
+
+    // Sum over the opponent hands that share no card with the player's hand.
+    // Sums that never contain the same hand may skip the opponent reach.
+    pub fn exclude(&self, player: Player, hand: usize, sum: f64, card_sums: &[f64], opponent_reach: Option<&[f64]>) -> f64 {
+        let cards = self.hand_cards(player, hand);
+        if cards[0] == cards[1] {
+            return sum - card_sums[cards[0]];
+        }
+
+        // The same hand holds both cards and was subtracted twice
+        let same = match (self.same_hand[player.as_usize()][hand], opponent_reach) {
+            (Some(same), Some(reach)) => reach[same],
+            _ => 0.0,
+        };
+
+        sum - card_sums[cards[0]] - card_sums[cards[1]] + same
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclude_blocked_hands() {
+        let hands = [
+            vec![HoleCards::new_from_string("AhKh"), HoleCards::new_from_string("QsQd")],
+            vec![HoleCards::new_from_string("AhKh"), HoleCards::new_from_string("AhQs"), HoleCards::new_from_string("JcJd")],
+        ];
+        let removal = CardRemoval::new(&hands);
+        let reach = [1.0, 2.0, 4.0];
+        let (total, card_sums) = removal.card_sums(Player::OOP, &reach);
+
+        assert_eq!(removal.exclude(Player::IP, 0, total, &card_sums, Some(&reach)), 4.0);
+        assert_eq!(removal.exclude(Player::IP, 1, total, &card_sums, Some(&reach)), 1.0 + 4.0);
+    }
+}
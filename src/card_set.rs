@@ -0,0 +1,150 @@
+use crate::board::Board;
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
+use crate::suit::Suit;
+use std::fmt;
+
+const SUIT_MASK: u64 = (1 << 13) - 1;
+
+// Set of cards of a standard deck as a bitmask of card indices. Each suit
+// takes 13 consecutive bits, so suit swaps exchange whole lanes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CardSet {
+    bits: u64,
+}
+
+impl CardSet {
+    pub fn new() -> Self {
+        CardSet { bits: 0 }
+    }
+
+    pub fn new_full() -> Self {
+        CardSet { bits: (1 << 52) - 1 }
+    }
+
+    pub fn from_cards(cards: &[Card]) -> Self {
+        let mut set = Self::new();
+        for card in cards {
+            set.insert(card);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, card: &Card) {
+        self.bits |= 1 << card.to_index();
+    }
+
+    pub fn remove(&mut self, card: &Card) {
+        self.bits &= !(1 << card.to_index());
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.bits & (1 << card.to_index()) != 0
+    }
+
+    pub fn union(&self, other: &CardSet) -> CardSet {
+        CardSet { bits: self.bits | other.bits }
+    }
+
+    pub fn difference(&self, other: &CardSet) -> CardSet {
+        CardSet { bits: self.bits & !other.bits }
+    }
+
+    pub fn intersects(&self, other: &CardSet) -> bool {
+        self.bits & other.bits != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn to_u64(&self) -> u64 {
+        self.bits
+    }
+
+    // The set with the cards of suits a and b exchanged
+    pub fn swap_suits(&self, a: Suit, b: Suit) -> CardSet {
+        let (a, b) = (Self::lane(a), Self::lane(b));
+        let lane_a = (self.bits >> a) & SUIT_MASK;
+        let lane_b = (self.bits >> b) & SUIT_MASK;
+        let rest = self.bits & !(SUIT_MASK << a) & !(SUIT_MASK << b);
+        CardSet { bits: rest | (lane_a << b) | (lane_b << a) }
+    }
+
+    // Cards by increasing index
+    pub fn to_vec(&self) -> Vec<Card> {
+        (0..52).filter(|i| self.bits & (1 << i) != 0).map(Card::from_index).collect()
+    }
+
+    fn lane(suit: Suit) -> u32 {
+        Card::new(2, suit).to_index() as u32
+    }
+}
+
+impl From<&Board> for CardSet {
+    fn from(board: &Board) -> Self {
+        Self::from_cards(&board.cards)
+    }
+}
+
+impl From<&HoleCards> for CardSet {
+    fn from(hole_cards: &HoleCards) -> Self {
+        Self::from_cards(&hole_cards.cards())
+    }
+}
+
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for card in self.to_vec() {
+            write!(f, "{:}", card)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_round_trip() {
+        let mut seen = CardSet::new();
+        for rank in 2..=14 {
+            for suit in Suit::to_vec() {
+                let card = Card::new(rank, suit);
+                assert_eq!(Card::from_index(card.to_index()), card);
+                assert!(!seen.contains(&card));
+                seen.insert(&card);
+            }
+        }
+
+        assert_eq!(seen, CardSet::new_full());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let hole_cards = HoleCards::new_from_string("AhKd");
+        let board = Board::from_vec(vec![Card::new(14, Suit::Spades), Card::new(13, Suit::Diamonds)]);
+        let set = CardSet::from(&hole_cards);
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Card::new(14, Suit::Hearts)));
+        assert!(set.intersects(&CardSet::from(&board)));
+        assert_eq!(set.union(&CardSet::from(&board)).len(), 3);
+        assert_eq!(set.difference(&CardSet::from(&board)).to_vec(), vec![Card::new(14, Suit::Hearts)]);
+    }
+
+    #[test]
+    fn test_swap_suits() {
+        let set = CardSet::from(&HoleCards::new_from_string("AhKd"));
+        let swapped = set.swap_suits(Suit::Hearts, Suit::Clubs);
+
+        assert_eq!(swapped, CardSet::from(&HoleCards::new_from_string("AcKd")));
+        assert_eq!(swapped.swap_suits(Suit::Hearts, Suit::Clubs), set);
+    }
+}
@@ -1,57 +1,295 @@
-use rand::SeedableRng;
-use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use crate::game::Game;
 use crate::cfr_visitor::CfrVisitor;
+use crate::discounted_cfr_visitor::DiscountedCfrVisitor;
+use crate::parallel_cfr_visitor::ParallelCfrVisitor;
+use crate::visitor::Visitor;
+use crate::training_method::TrainingMethod;
+use crate::player::Player;
+use crate::cfr_config::CfrConfig;
+use crate::cfr_variant::CfrVariant;
 use crate::game_tree::GameTree;
-use crate::info_state::InfoState;
 use crate::tree_walker::TreeWalker;
+use crate::tree_arena::TreeArena;
+use crate::range_walker::RangeWalker;
 use crate::statistics_visitor::StatisticsVisitor;
+use crate::checkpoint::Checkpoint;
+use crate::checkpoint_interval::CheckpointInterval;
+use crate::deal::Deal;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+
+type ThreadResult = (f64, HashMap<usize, Vec<f64>>, HashMap<usize, Vec<f64>>);
 
 pub struct CFR<G: Game + Clone> {
     game: G,
-    rng: StdRng,
     tree: GameTree<G>,
+    config: CfrConfig,
+    iteration: usize,
+    last_checkpoint: Instant,
+    // One stream for the whole solve, its position is saved in checkpoints
+    rng: ChaCha12Rng,
+    // Only built for range vs range training
+    range_walker: Option<RangeWalker>,
 }
 
-impl<G: Game + Clone> CFR<G> {
+impl<G: Game + Clone + Sync> CFR<G> {
     pub fn new(game: G) -> Self {
+        Self::new_with_config(game, CfrConfig::new_default())
+    }
+
+    pub fn new_with_config(game: G, config: CfrConfig) -> Self {
         let mut tree = GameTree::new(game.clone());
         tree.build();
 
+        Self::new_with_tree(tree, config)
+    }
+
+    // Continues training from an existing, e.g. loaded, tree
+    pub fn new_with_tree(tree: GameTree<G>, config: CfrConfig) -> Self {
+        let range_walker = match config.method {
+            TrainingMethod::RangeVsRange => {
+                assert!(tree.game.supports_range_vs_range(), "{} does not support range vs range walks", tree.game.signature());
+                Some(RangeWalker::new(&tree.game, &tree.arena))
+            }
+            _ => None,
+        };
+
         CFR {
-            game,
-            rng: StdRng::seed_from_u64(0),
+            game: tree.game.clone(),
             tree,
+            rng: ChaCha12Rng::seed_from_u64(config.seed),
+            config,
+            iteration: 0,
+            last_checkpoint: Instant::now(),
+            range_walker,
+        }
+    }
+
+    // Continues training from a checkpoint written with the same game and config.
+    // The seed stored in the checkpoint is used.
+    pub fn new_from_checkpoint(game: G, config: CfrConfig, path: &Path) -> io::Result<Self> {
+        let checkpoint = Checkpoint::load(path, &Self::checkpoint_signature(&game, &config))?;
+        let tree = GameTree::new_with_tables(game, checkpoint.regrets, checkpoint.strategy_sum)?;
+
+        let mut cfr = Self::new_with_tree(tree, config);
+        cfr.config.seed = checkpoint.seed;
+        cfr.iteration = checkpoint.iteration;
+        cfr.rng = ChaCha12Rng::seed_from_u64(checkpoint.seed);
+        cfr.rng.set_word_pos(checkpoint.rng_position);
+        Ok(cfr)
+    }
+
+    pub fn save_checkpoint(&self, path: &Path) -> io::Result<()> {
+        let checkpoint = Checkpoint {
+            seed: self.config.seed,
+            rng_position: self.rng.get_word_pos(),
+            iteration: self.iteration,
+            regrets: self.tree.regrets.clone(),
+            strategy_sum: self.tree.strategy_sum.clone(),
+        };
+
+        checkpoint.save(path, &Self::checkpoint_signature(&self.game, &self.config))
+    }
+
+    // Resuming is only identical with the same variant, method and thread count
+    fn checkpoint_signature(game: &G, config: &CfrConfig) -> String {
+        format!("{} {:?} {:?} threads={}", game.signature(), config.variant, config.method, config.threads)
+    }
+
+    fn checkpoint_if_due(&mut self) {
+        let checkpoint = match &self.config.checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => return,
+        };
+
+        let due = match checkpoint.interval {
+            CheckpointInterval::Iterations(n) => self.iteration.is_multiple_of(n),
+            CheckpointInterval::Seconds(n) => self.last_checkpoint.elapsed().as_secs() >= n,
+        };
+
+        if due {
+            // A failed checkpoint should not end the solve
+            if let Err(e) = self.save_checkpoint(&checkpoint.path) {
+                eprintln!("Failed to write checkpoint {}: {}", checkpoint.path.display(), e);
+            }
+            self.last_checkpoint = Instant::now();
         }
     }
 
     pub fn train_for_iters(&mut self, iterations: usize) -> f64 {
         let mut ev = 0.0;
-        let mut visitor = CfrVisitor::new(&mut self.tree.regrets, &mut self.tree.strategy_sum);
         for _ in 0..iterations {
-            ev += TreeWalker::monte_carlo_iterate(&self.game, &mut self.rng, &mut visitor);
+            self.iteration += 1;
+            // Full walks always buffer their updates, so every deal of an
+            // iteration plays the same strategy
+            let buffered = self.config.threads > 1 || self.config.method == TrainingMethod::Full;
+            ev += if buffered && self.range_walker.is_none() {
+                self.iterate_parallel()
+            } else {
+                self.iterate()
+            };
+            self.checkpoint_if_due();
         }
 
         return ev / iterations as f64;
     }
 
+    fn iterate(&mut self) -> f64 {
+        let arena = &self.tree.arena;
+        let regrets = &mut self.tree.regrets;
+        let strategy_sum = &mut self.tree.strategy_sum;
+        let method = self.config.method;
+        let rng = &mut self.rng;
+
+        if let Some(range_walker) = &self.range_walker {
+            return range_walker.iterate(arena, regrets, strategy_sum, self.config.variant, self.iteration);
+        }
+
+        match self.config.variant {
+            CfrVariant::Vanilla => {
+                let mut visitor = CfrVisitor::new(regrets, strategy_sum);
+                Self::walk(&self.game, arena, rng, method, self.iteration, &mut visitor)
+            }
+            CfrVariant::Plus => {
+                let mut visitor = CfrVisitor::new_plus(regrets, strategy_sum, self.iteration);
+                Self::walk(&self.game, arena, rng, method, self.iteration, &mut visitor)
+            }
+            CfrVariant::Discounted { alpha, beta, gamma } => {
+                let mut visitor = DiscountedCfrVisitor::new(
+                    regrets, strategy_sum, self.iteration, alpha, beta, gamma);
+                visitor.discount_all();
+                Self::walk(&self.game, arena, rng, method, self.iteration, &mut visitor)
+            }
+        }
+    }
+
+    // Full walks split the deals between the threads, also with a single
+    // thread. Sampling methods sample one deal per thread. The updates of the threads are added
+    // in thread order, so results only depend on the thread count.
+    fn iterate_parallel(&mut self) -> f64 {
+        let strategy_weight = match self.config.variant {
+            CfrVariant::Vanilla => 1.0,
+            CfrVariant::Plus => self.iteration as f64,
+            CfrVariant::Discounted { alpha, beta, gamma } => {
+                DiscountedCfrVisitor::new(
+                    &mut self.tree.regrets, &mut self.tree.strategy_sum, self.iteration, alpha, beta, gamma)
+                    .discount_all();
+                1.0
+            }
+        };
+
+        let threads = self.config.threads;
+        let method = self.config.method;
+        let deals = match method {
+            TrainingMethod::Full => self.game.generate_deals(),
+            _ => Vec::new(),
+        };
+        let shares: Vec<&[Deal]> = match method {
+            TrainingMethod::Full => deals.chunks(deals.len().div_ceil(threads).max(1)).collect(),
+            _ => vec![&[]; threads],
+        };
+
+        let game = &self.game;
+        let arena = &self.tree.arena;
+        let regrets = &self.tree.regrets;
+        let iteration = self.iteration;
+        // Each thread samples from its own generator seeded from the stream
+        let seeds: Vec<u64> = match method {
+            TrainingMethod::Full => vec![0; threads],
+            _ => (0..threads).map(|_| self.rng.gen()).collect(),
+        };
+        let results: Vec<ThreadResult> = std::thread::scope(|scope| {
+            let handles: Vec<_> = shares.iter().zip(seeds.iter()).map(|(share, &seed)| {
+                let deal_count = deals.len();
+                scope.spawn(move || {
+                    let mut visitor = ParallelCfrVisitor::new(regrets, strategy_weight);
+                    let ev = match method {
+                        TrainingMethod::Full => {
+                            TreeWalker::walk_deals(game, arena, share, &mut visitor) * share.len() as f64 / deal_count as f64
+                        }
+                        _ => {
+                            let mut rng = ChaCha12Rng::seed_from_u64(seed);
+                            Self::walk(game, arena, &mut rng, method, iteration, &mut visitor) / threads as f64
+                        }
+                    };
+
+                    (ev, visitor.regret_updates, visitor.strategy_updates)
+                })
+            }).collect();
+
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        let mut ev = 0.0;
+        for (thread_ev, regret_updates, strategy_updates) in results {
+            ev += thread_ev;
+            Self::add_updates(&mut self.tree.regrets, regret_updates);
+            Self::add_updates(&mut self.tree.strategy_sum, strategy_updates);
+        }
+
+        if self.config.variant == CfrVariant::Plus {
+            for regret in self.tree.regrets.iter_mut() {
+                *regret = regret.max(0.0);
+            }
+        }
+
+        ev
+    }
+
+    fn add_updates(table: &mut [f64], updates: HashMap<usize, Vec<f64>>) {
+        for (offset, values) in updates {
+            for (value, update) in table[offset..offset + values.len()].iter_mut().zip(values.iter()) {
+                *value += update;
+            }
+        }
+    }
+
+    fn walk<V: Visitor>(game: &G, arena: &TreeArena, rng: &mut ChaCha12Rng, method: TrainingMethod, iteration: usize, visitor: &mut V) -> f64 {
+        match method {
+            TrainingMethod::MonteCarlo => TreeWalker::monte_carlo_iterate(game, arena, rng, visitor),
+            TrainingMethod::Full => unreachable!("Full walks buffer their updates"),
+            TrainingMethod::ExternalSampling => {
+                TreeWalker::external_sampling_iterate(game, arena, rng, Self::traverser(iteration), visitor)
+            }
+            TrainingMethod::OutcomeSampling { epsilon } => {
+                TreeWalker::outcome_sampling_iterate(game, arena, rng, Self::traverser(iteration), epsilon, visitor)
+            }
+            TrainingMethod::RangeVsRange => unreachable!("Range vs range walks do not use visitors"),
+        }
+    }
+
+    fn traverser(iteration: usize) -> Player {
+        if iteration % 2 == 1 { Player::OOP } else { Player::IP }
+    }
+
+    // Threshold is in mbb/hand
     pub fn train_to_exploitability(&mut self, threshold: f64) -> f64 {
         let mut ev = 0.0;
         let mut exploitability = f64::INFINITY;
         let iteration_size = 200;
-        let mut total_iterations = 0;
 
         while exploitability > threshold {
             ev = self.train_for_iters(iteration_size);
             let statistics = self.build_statistics();
-            exploitability = statistics.node_exploitability(&InfoState::new_empty());
-            total_iterations += iteration_size;
-            println!("Total iterations: {}, exploitability: {:.2?} %", total_iterations, exploitability);
+            exploitability = statistics.exploitability_mbb();
+            println!("Total iterations: {}, exploitability: {:.2?} mbb/hand", self.iteration, exploitability);
         }
 
         return ev;
     }
 
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    pub fn tree(&self) -> &GameTree<G> {
+        &self.tree
+    }
+
     pub fn print_strategy(&mut self) {
         self.tree.print_tree();
     }
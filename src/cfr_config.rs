@@ -0,0 +1,37 @@
+use crate::cfr_variant::CfrVariant;
+use crate::training_method::TrainingMethod;
+use crate::checkpoint_config::CheckpointConfig;
+
+#[derive(Clone, Debug)]
+pub struct CfrConfig {
+    pub variant: CfrVariant,
+    pub method: TrainingMethod,
+    // Seed of the per-iteration random number generators
+    pub seed: u64,
+    // Threads walking deals in parallel. With more than one thread, all
+    // threads play the strategy of the start of the iteration.
+    pub threads: usize,
+    pub checkpoint: Option<CheckpointConfig>,
+}
+
+impl CfrConfig {
+    pub fn new_default() -> Self {
+        CfrConfig {
+            variant: CfrVariant::Vanilla,
+            method: TrainingMethod::MonteCarlo,
+            seed: 0,
+            threads: 1,
+            checkpoint: None,
+        }
+    }
+
+    pub fn new_with_variant(variant: CfrVariant) -> Self {
+        CfrConfig {
+            variant,
+            method: TrainingMethod::MonteCarlo,
+            seed: 0,
+            threads: 1,
+            checkpoint: None,
+        }
+    }
+}
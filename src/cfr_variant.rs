@@ -0,0 +1,17 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CfrVariant {
+    // Plain regret matching with uniformly weighted average strategy
+    Vanilla,
+    // Regrets floored at zero, average strategy weighted by iteration
+    Plus,
+    // Positive regrets, negative regrets and strategy contributions
+    // discounted by t^alpha/(t^alpha+1), t^beta/(t^beta+1) and (t/(t+1))^gamma
+    Discounted { alpha: f64, beta: f64, gamma: f64 },
+}
+
+impl CfrVariant {
+    // Parameters recommended by Brown & Sandholm
+    pub fn new_discounted_default() -> Self {
+        CfrVariant::Discounted { alpha: 1.5, beta: 0.0, gamma: 2.0 }
+    }
+}
@@ -1,56 +1,115 @@
 use crate::visitor::Visitor;
 use crate::node::Node;
-use crate::info_state::InfoState;
-use std::collections::HashMap;
+use crate::utils::Utils;
 
 pub struct CfrVisitor<'a> {
-    regrets: &'a mut HashMap<InfoState, Vec<f64>>,
-    strategy_sum: &'a mut HashMap<InfoState, Vec<f64>>,
+    regrets: &'a mut [f64],
+    strategy_sum: &'a mut [f64],
+    plus: bool,
+    iteration: usize,
 }
 
 impl<'a> CfrVisitor<'a> {
     pub fn new(
-                regrets: &'a mut HashMap<InfoState, Vec<f64>>,
-                strategy_sum: &'a mut HashMap<InfoState, Vec<f64>>) -> Self {
+                regrets: &'a mut [f64],
+                strategy_sum: &'a mut [f64]) -> Self {
         CfrVisitor {
             regrets,
             strategy_sum,
+            plus: false,
+            iteration: 0,
+        }
+    }
+
+    // CFR+ floors regrets at zero after every update and
+    // weights the strategy contribution of iteration t by t.
+    pub fn new_plus(
+                regrets: &'a mut [f64],
+                strategy_sum: &'a mut [f64],
+                iteration: usize) -> Self {
+        CfrVisitor {
+            regrets,
+            strategy_sum,
+            plus: true,
+            iteration,
+        }
+    }
+
+    fn strategy_weight(&self) -> f64 {
+        if self.plus { self.iteration as f64 } else { 1.0 }
+    }
+
+    fn update_strategy_sum(&mut self, node: &Node, reach_prob: f64) {
+        let weight = self.strategy_weight();
+        let strategy_sum = &mut self.strategy_sum[node.buffer_range()];
+        for (sum, prob) in strategy_sum.iter_mut().zip(node.action_probs.iter()) {
+            *sum += weight * reach_prob * prob;
+        }
+    }
+
+    fn update_regrets(&mut self, node: &Node) {
+        let regrets = &mut self.regrets[node.buffer_range()];
+        for (regret, action_util) in regrets.iter_mut().zip(node.action_utils.iter()) {
+            *regret += node.opponent_reach_prob() * node.importance_weight() * (action_util - node.util);
+
+            if self.plus && *regret < 0.0 {
+                *regret = 0.0;
+            }
         }
     }
 }
 
 impl<'a> Visitor for CfrVisitor<'a> {
     fn visit_action_node(&mut self, node: &Node) {
-        for i in 0..node.action_probs.len() {
-            self.strategy_sum.get_mut(&node.info_state()).unwrap()[i] +=
-                node.player_reach_prob() * node.action_probs[i];
-        }
+        self.update_strategy_sum(node, node.player_reach_prob());
+        self.update_regrets(node);
+    }
 
-        for i in 0..node.actions.len() {
-            let regret = node.action_utils[i] - node.util;
-            self.regrets.get_mut(&node.info_state()).unwrap()[i] +=
-                node.opponent_reach_prob() * regret;
-        }
+    fn visit_traverser_node(&mut self, node: &Node) {
+        self.update_regrets(node);
+    }
+
+    fn visit_sampled_action_node(&mut self, node: &Node) {
+        // The player's own actions were sampled, so reach is already accounted for
+        self.update_strategy_sum(node, node.importance_weight());
     }
 
     fn get_action_probs(&self, node: &Node) -> Vec<f64> {
-        let regrets = self.regrets.get(&node.info_state()).unwrap();
-        let mut strategy: Vec<f64> = node.zero_utils();
-        let mut normalizing_sum = 0.0;
+        let regrets = &self.regrets[node.buffer_range()];
+        Utils::regret_matching(regrets)
+    }
+}
 
-        for (i, regret) in regrets.iter().enumerate() {
-            strategy[i] = if *regret > 0.0 { *regret } else { 0.0 };
-            normalizing_sum += strategy[i];
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deal::Deal;
 
-        for i in 0..strategy.len() {
-            strategy[i] = if normalizing_sum > 0.0 {
-                strategy[i] / normalizing_sum
-            } else {
-                1.0 / strategy.len() as f64
-            };
-        }
+    #[test]
+    fn test_vanilla_keeps_negative_regrets() {
+        let deal = Deal::new_kuhn();
+        let node = Node::new_kuhn_root(&deal);
+        let mut regrets = vec![0.0, 0.0];
+        let mut strategy_sum = vec![0.0, 0.0];
+
+        let mut visitor = CfrVisitor::new(&mut regrets, &mut strategy_sum);
+        visitor.visit_action_node(&node);
 
-        strategy
+        assert_eq!(regrets, vec![2.0, -2.0]);
+        assert_eq!(strategy_sum, vec![0.5, 0.5]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_plus_floors_regrets_and_weights_strategy() {
+        let deal = Deal::new_kuhn();
+        let node = Node::new_kuhn_root(&deal);
+        let mut regrets = vec![0.0, 0.0];
+        let mut strategy_sum = vec![0.0, 0.0];
+
+        let mut visitor = CfrVisitor::new_plus(&mut regrets, &mut strategy_sum, 3);
+        visitor.visit_action_node(&node);
+
+        assert_eq!(regrets, vec![2.0, 0.0]);
+        assert_eq!(strategy_sum, vec![1.5, 1.5]);
+    }
+}
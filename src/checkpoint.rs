@@ -0,0 +1,81 @@
+use crate::tree_file::{Table, TreeFileWriter, TreeFileReader, invalid_data};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"RSCP";
+
+// Training state needed to resume CFR. The seed and the position in its
+// stream determine the RNG state.
+#[derive(Debug, PartialEq)]
+pub struct Checkpoint {
+    pub seed: u64,
+    // Words of the seed's stream drawn so far
+    pub rng_position: u128,
+    pub iteration: usize,
+    pub regrets: Table,
+    pub strategy_sum: Table,
+}
+
+impl Checkpoint {
+    // Written to a temporary file first so a crash never leaves a partial checkpoint
+    pub fn save(&self, path: &Path, signature: &str) -> io::Result<()> {
+        let mut writer = TreeFileWriter::new();
+        writer.header(MAGIC, signature);
+        writer.u64(self.seed);
+        writer.u64(self.rng_position as u64);
+        writer.u64((self.rng_position >> 64) as u64);
+        writer.u64(self.iteration as u64);
+        writer.table(&self.regrets);
+        writer.table(&self.strategy_sum);
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, writer.bytes)?;
+        fs::rename(&tmp_path, path)
+    }
+
+    pub fn load(path: &Path, signature: &str) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let mut reader = TreeFileReader::new(&bytes);
+        reader.header(MAGIC, signature)?;
+        let checkpoint = Checkpoint {
+            seed: reader.u64()?,
+            rng_position: reader.u64()? as u128 | (reader.u64()? as u128) << 64,
+            iteration: reader.u64()? as usize,
+            regrets: reader.table()?,
+            strategy_sum: reader.table()?,
+        };
+
+        if !reader.is_empty() {
+            return Err(invalid_data("Trailing bytes after strategy tables".to_string()));
+        }
+
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join("rustysolver_test_checkpoint_save_and_load.bin");
+        let checkpoint = Checkpoint {
+            seed: 7,
+            rng_position: (3 << 64) + 5,
+            iteration: 1234,
+            regrets: vec![1.0, -2.0],
+            strategy_sum: vec![0.5, 0.25],
+        };
+        checkpoint.save(&path, "Kuhn").unwrap();
+
+        let loaded = Checkpoint::load(&path, "Kuhn");
+        let other_game = Checkpoint::load(&path, "Leduc");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), checkpoint);
+        assert_eq!(other_game.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
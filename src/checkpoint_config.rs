@@ -0,0 +1,17 @@
+use crate::checkpoint_interval::CheckpointInterval;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub struct CheckpointConfig {
+    pub path: PathBuf,
+    pub interval: CheckpointInterval,
+}
+
+impl CheckpointConfig {
+    pub fn new(path: PathBuf, interval: CheckpointInterval) -> Self {
+        CheckpointConfig {
+            path,
+            interval,
+        }
+    }
+}
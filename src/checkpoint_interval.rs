@@ -0,0 +1,7 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckpointInterval {
+    // Checkpoint after every n-th iteration
+    Iterations(usize),
+    // Checkpoint after the first iteration that ends n seconds after the last checkpoint
+    Seconds(u64),
+}
@@ -33,4 +33,17 @@ impl Deal {
             history: History::new(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+impl Deal {
+    // Jack against queen in Kuhn
+    pub fn new_kuhn() -> Self {
+        Deal::new(
+            PlayerCards::new(HoleCards::new_with_ranks(1, 1), HoleCards::new_with_ranks(2, 2)),
+            Deck::new_empty(),
+            (1.0, 1.0),
+            History::new()
+        )
+    }
+}
@@ -1,12 +1,16 @@
 use crate::card::Card;
+use crate::card_set::CardSet;
 use crate::suit::Suit;
 use rand::seq::SliceRandom;
-use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 use std::fmt;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Deck {
     cards: Vec<Card>,
+    // Cards removed by value. They stay in the list and are skipped, so
+    // removing them is a bit operation.
+    removed: CardSet,
 }
 
 impl Deck {
@@ -17,18 +21,18 @@ impl Deck {
                 cards.push(Card::new(rank, suit));
             }
         }
-        Deck { cards }
+        Self::new_from_cards(cards)
     }
 
     pub fn new_from_cards(cards: Vec<Card>) -> Self {
-        Deck { cards }
+        Deck { cards, removed: CardSet::new() }
     }
 
     pub fn new_empty() -> Self {
-        Deck { cards: Vec::new() }
+        Self::new_from_cards(Vec::new())
     }
 
-    pub fn shuffle(&mut self, rng: &mut StdRng) {
+    pub fn shuffle(&mut self, rng: &mut ChaCha12Rng) {
         self.cards.shuffle(rng);
     }
 
@@ -37,40 +41,108 @@ impl Deck {
     }
 
     pub fn draw(&mut self) -> Option<Card> {
-        self.cards.pop()
+        while let Some(card) = self.cards.pop() {
+            if !self.is_removed(&card) {
+                return Some(card);
+            }
+        }
+
+        None
     }
 
     pub fn to_vec(&self) -> Vec<Card> {
-        self.cards.clone()
+        self.iter().cloned().collect()
     }
 
     pub fn len(&self) -> usize {
-        self.cards.len()
+        self.iter().count()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Card> {
-        self.cards.iter()
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter().filter(|card| !self.is_removed(card))
     }
 
+    // Cards without an index, rank 1 or meta suits, are taken out of the list
     pub fn remove(&mut self, card: &Card) {
-        self.cards.retain(|c| c != card);
+        if card.has_index() {
+            self.removed.insert(card);
+        } else {
+            self.cards.retain(|c| c != card);
+        }
+    }
+
+    pub fn remove_all(&mut self, cards: &CardSet) {
+        self.removed = self.removed.union(cards);
     }
 
     pub fn get(&self, index: usize) -> Option<Card> {
-        self.cards.get(index).cloned()
+        self.iter().nth(index).cloned()
     }
 
     pub fn remove_index(&mut self, index: usize) {
-        self.cards.remove(index);
+        let position = self.cards.iter().enumerate()
+            .filter(|(_, card)| !self.is_removed(card))
+            .nth(index)
+            .map(|(position, _)| position)
+            .unwrap_or_else(|| panic!("No card at index {}", index));
+        self.cards.remove(position);
+    }
+
+    fn is_removed(&self, card: &Card) -> bool {
+        card.has_index() && self.removed.contains(card)
+    }
+}
+
+impl PartialEq for Deck {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
     }
 }
 
 impl fmt::Display for Deck {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for card in &self.cards {
+        for card in self.iter() {
             write!(f, "{:}", card)?;
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuhn::Kuhn;
+    use crate::game::Game;
+
+    #[test]
+    fn test_removed_cards_are_skipped() {
+        let (ace, king) = (Card::new(14, Suit::Spades), Card::new(13, Suit::Hearts));
+        let mut deck = Deck::new();
+        deck.remove(&ace);
+        deck.remove_all(&CardSet::from_cards(&[king.clone()]));
+
+        assert_eq!(deck.len(), 50);
+        assert!(!deck.iter().any(|card| *card == ace || *card == king));
+        assert_eq!(deck.draw(), Some(Card::new(14, Suit::Clubs)));
+    }
+
+    #[test]
+    fn test_remove_card_without_index() {
+        let jack = Card::new(1, Suit::Diamonds);
+        let mut deck = Deck::new_from_cards(vec![jack.clone(), Card::new(2, Suit::Diamonds), jack.clone()]);
+        deck.remove(&jack);
+
+        assert_eq!(deck.to_vec(), vec![Card::new(2, Suit::Diamonds)]);
+    }
+
+    #[test]
+    fn test_remove_kuhn_card_with_index() {
+        let queen = Card::new(2, Suit::Diamonds);
+        let mut deck = Kuhn::new().deck();
+        deck.remove(&queen);
+
+        assert_eq!(deck.len(), 2);
+        assert_eq!(deck.to_vec(), vec![Card::new(1, Suit::Diamonds), Card::new(3, Suit::Diamonds)]);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::visitor::Visitor;
+use crate::node::Node;
+use crate::utils::Utils;
+
+// Discounted CFR. Accumulated regrets and strategy sums are discounted
+// with discount_all once at the start of each iteration.
+pub struct DiscountedCfrVisitor<'a> {
+    regrets: &'a mut [f64],
+    strategy_sum: &'a mut [f64],
+    positive_discount: f64,
+    negative_discount: f64,
+    strategy_discount: f64,
+}
+
+impl<'a> DiscountedCfrVisitor<'a> {
+    pub fn new(
+                regrets: &'a mut [f64],
+                strategy_sum: &'a mut [f64],
+                iteration: usize,
+                alpha: f64,
+                beta: f64,
+                gamma: f64) -> Self {
+        // Discount what was accumulated up to the previous iteration
+        let t = iteration.saturating_sub(1) as f64;
+
+        DiscountedCfrVisitor {
+            regrets,
+            strategy_sum,
+            positive_discount: t.powf(alpha) / (t.powf(alpha) + 1.0),
+            negative_discount: t.powf(beta) / (t.powf(beta) + 1.0),
+            strategy_discount: (t / (t + 1.0)).powf(gamma),
+        }
+    }
+
+    // Discounts every info state, before the walk of the iteration
+    pub fn discount_all(&mut self) {
+        for regret in self.regrets.iter_mut() {
+            *regret *= if *regret > 0.0 { self.positive_discount } else { self.negative_discount };
+        }
+
+        for sum in self.strategy_sum.iter_mut() {
+            *sum *= self.strategy_discount;
+        }
+    }
+
+    fn update_strategy_sum(&mut self, node: &Node, reach_prob: f64) {
+        let strategy_sum = &mut self.strategy_sum[node.buffer_range()];
+        for (sum, prob) in strategy_sum.iter_mut().zip(node.action_probs.iter()) {
+            *sum += reach_prob * prob;
+        }
+    }
+
+    fn update_regrets(&mut self, node: &Node) {
+        let regrets = &mut self.regrets[node.buffer_range()];
+        for (regret, action_util) in regrets.iter_mut().zip(node.action_utils.iter()) {
+            *regret += node.opponent_reach_prob() * node.importance_weight() * (action_util - node.util);
+        }
+    }
+}
+
+impl<'a> Visitor for DiscountedCfrVisitor<'a> {
+    fn visit_action_node(&mut self, node: &Node) {
+        self.update_strategy_sum(node, node.player_reach_prob());
+        self.update_regrets(node);
+    }
+
+    fn visit_traverser_node(&mut self, node: &Node) {
+        self.update_regrets(node);
+    }
+
+    fn visit_sampled_action_node(&mut self, node: &Node) {
+        self.update_strategy_sum(node, node.importance_weight());
+    }
+
+    fn get_action_probs(&self, node: &Node) -> Vec<f64> {
+        let regrets = &self.regrets[node.buffer_range()];
+        Utils::regret_matching(regrets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deal::Deal;
+
+    #[test]
+    fn test_visits_do_not_discount() {
+        let deal = Deal::new_kuhn();
+        let node = Node::new_kuhn_root(&deal);
+        let mut regrets = vec![4.0, -4.0];
+        let mut strategy_sum = vec![2.0, 2.0];
+
+        let mut visitor = DiscountedCfrVisitor::new(&mut regrets, &mut strategy_sum, 2, 1.0, 0.0, 2.0);
+        visitor.visit_action_node(&node);
+
+        assert_eq!(regrets, vec![4.0 + 2.0, -4.0 - 2.0]);
+        assert_eq!(strategy_sum, vec![2.0 + 0.5, 2.0 + 0.5]);
+    }
+
+    #[test]
+    fn test_discount_all() {
+        let deal = Deal::new_kuhn();
+        let node = Node::new_kuhn_root(&deal);
+        let mut regrets = vec![4.0, -4.0];
+        let mut strategy_sum = vec![2.0, 2.0];
+
+        // t = 1 for discounting: positive 1/2, negative 1/2, strategy 1/4
+        let mut visitor = DiscountedCfrVisitor::new(&mut regrets, &mut strategy_sum, 2, 1.0, 0.0, 2.0);
+        visitor.discount_all();
+        visitor.visit_action_node(&node);
+
+        assert_eq!(regrets, vec![2.0 + 2.0, -2.0 - 2.0]);
+        assert_eq!(strategy_sum, vec![0.5 + 0.5, 0.5 + 0.5]);
+    }
+
+    #[test]
+    fn test_first_iteration_discards_nothing_accumulated() {
+        let deal = Deal::new_kuhn();
+        let node = Node::new_kuhn_root(&deal);
+        let mut regrets = vec![0.0, 0.0];
+        let mut strategy_sum = vec![0.0, 0.0];
+
+        let mut visitor = DiscountedCfrVisitor::new(&mut regrets, &mut strategy_sum, 1, 1.5, 0.0, 2.0);
+        visitor.visit_action_node(&node);
+
+        assert_eq!(regrets, vec![2.0, -2.0]);
+        assert_eq!(strategy_sum, vec![0.5, 0.5]);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::board::Board;
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::deck::Deck;
+use crate::hand_evaluator::player_wins;
+use crate::hole_cards::HoleCards;
+use crate::range::Range;
+use rand_chacha::ChaCha12Rng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+// Share of the pot a hand wins at showdown, ties counting half. Turn and
+// river boards are enumerated exactly, preflop and flop boards are sampled.
+// Opponent hands sharing a card with the hand or the board are removed.
+#[derive(Clone, Debug)]
+pub struct Equity {
+    // Runouts sampled per hand on preflop and flop boards
+    pub samples: usize,
+    pub seed: u64,
+}
+
+impl Equity {
+    pub fn new(samples: usize, seed: u64) -> Self {
+        Equity {
+            samples,
+            seed,
+        }
+    }
+
+    pub fn new_default() -> Self {
+        Self::new(10000, 0)
+    }
+
+    // Equity of the hand against the range, None if every opponent hand
+    // is blocked
+    pub fn hand_vs_range(&self, hand: &HoleCards, range: &Range, board: &Board) -> Option<f64> {
+        let dead = CardSet::from(hand).union(&CardSet::from(board));
+        let opponents: Vec<(HoleCards, f64)> = range.expand_meta_suits().iter()
+            .filter(|(opponent, weight)| **weight > 0.0 && !CardSet::from(*opponent).intersects(&dead))
+            .map(|(opponent, weight)| (opponent.clone(), *weight))
+            .collect();
+        if opponents.is_empty() {
+            return None;
+        }
+
+        if board.cards.len() >= 4 {
+            Some(Self::exact(hand, &opponents, board, &dead))
+        } else {
+            Some(self.sampled(hand, &opponents, board, &dead))
+        }
+    }
+
+    // Equity of the hand against a single opponent hand, neither sharing a
+    // card with the other or the board
+    pub fn hand_vs_hand(&self, hand: &HoleCards, opponent: &HoleCards, board: &Board) -> f64 {
+        let dead = CardSet::from(hand).union(&CardSet::from(board));
+        assert!(!CardSet::from(opponent).intersects(&dead), "{} is blocked by {} and the board", opponent, hand);
+        let opponents = [(opponent.clone(), 1.0)];
+
+        if board.cards.len() >= 4 {
+            Self::exact(hand, &opponents, board, &dead)
+        } else {
+            self.sampled(hand, &opponents, board, &dead)
+        }
+    }
+
+    // Equity of every hand of the range not blocked by the board against
+    // the opponent range
+    pub fn range_vs_range(&self, range: &Range, opponent: &Range, board: &Board) -> HashMap<HoleCards, f64> {
+        let board_cards = CardSet::from(board);
+        range.expand_meta_suits().iter()
+            .filter(|(hand, weight)| **weight > 0.0 && !CardSet::from(*hand).intersects(&board_cards))
+            .filter_map(|(hand, _)| self.hand_vs_range(hand, opponent, board).map(|equity| (hand.clone(), equity)))
+            .collect()
+    }
+
+    // Equity of the range against the opponent range, each hand weighted by
+    // its own weight and the weight of the opponent hands it does not block.
+    // None if every pair of hands is blocked.
+    pub fn range_equity(&self, range: &Range, opponent: &Range, board: &Board) -> Option<f64> {
+        let range = range.expand_meta_suits();
+        let opponent = opponent.expand_meta_suits();
+        let board_cards = CardSet::from(board);
+
+        let mut total = 0.0;
+        let mut total_weight = 0.0;
+        for (hand, equity) in self.range_vs_range(&range, &opponent, board) {
+            let cards = CardSet::from(&hand).union(&board_cards);
+            let opponent_weight: f64 = opponent.iter()
+                .filter(|(opponent_hand, _)| !CardSet::from(*opponent_hand).intersects(&cards))
+                .map(|(_, weight)| weight)
+                .sum();
+            let weight = range.range[&hand] * opponent_weight;
+            total += weight * equity;
+            total_weight += weight;
+        }
+
+        if total_weight == 0.0 {
+            return None;
+        }
+        Some(total / total_weight)
+    }
+
+    fn showdown(hand: &HoleCards, opponent: &HoleCards, board: Board) -> f64 {
+        match player_wins(hand.clone(), opponent.clone(), board) {
+            Some(true) => 1.0,
+            Some(false) => 0.0,
+            None => 0.5,
+        }
+    }
+
+    fn exact(hand: &HoleCards, opponents: &[(HoleCards, f64)], board: &Board, dead: &CardSet) -> f64 {
+        let mut total = 0.0;
+        let mut total_weight = 0.0;
+        for (opponent, weight) in opponents {
+            let mut deck = Deck::new();
+            deck.remove_all(&dead.union(&CardSet::from(opponent)));
+            let runouts = Self::runouts(&deck.to_vec(), 5 - board.cards.len());
+
+            let wins: f64 = runouts.iter()
+                .map(|runout| {
+                    let mut full_board = board.clone();
+                    for card in runout {
+                        full_board.push(card.clone());
+                    }
+                    Self::showdown(hand, opponent, full_board)
+                })
+                .sum();
+            total += weight * wins / runouts.len() as f64;
+            total_weight += weight;
+        }
+
+        total / total_weight
+    }
+
+    // All ways to deal `count` cards from the deck, in deck order
+    fn runouts(deck: &[Card], count: usize) -> Vec<Vec<Card>> {
+        if count == 0 {
+            return vec![vec![]];
+        }
+
+        let mut runouts = Vec::new();
+        for (i, card) in deck.iter().enumerate() {
+            for mut rest in Self::runouts(&deck[i + 1..], count - 1) {
+                rest.insert(0, card.clone());
+                runouts.push(rest);
+            }
+        }
+        runouts
+    }
+
+    fn sampled(&self, hand: &HoleCards, opponents: &[(HoleCards, f64)], board: &Board, dead: &CardSet) -> f64 {
+        let mut rng = ChaCha12Rng::seed_from_u64(self.seed);
+        let total_weight: f64 = opponents.iter().map(|(_, weight)| weight).sum();
+        let deck = Deck::new();
+
+        let mut wins = 0.0;
+        for _ in 0..self.samples {
+            // Opponent hand drawn by weight
+            let mut pick = rng.gen::<f64>() * total_weight;
+            let (opponent, _) = opponents.iter()
+                .find(|(_, weight)| { pick -= weight; pick < 0.0 })
+                .unwrap_or(&opponents[opponents.len() - 1]);
+
+            let used = dead.union(&CardSet::from(opponent));
+            let remaining: Vec<Card> = deck.iter().filter(|card| !used.contains(card)).cloned().collect();
+            let mut full_board = board.clone();
+            for card in remaining.choose_multiple(&mut rng, 5 - board.cards.len()) {
+                full_board.push(card.clone());
+            }
+
+            wins += Self::showdown(hand, opponent, full_board);
+        }
+
+        wins / self.samples as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(s: &str) -> Board {
+        let chars: Vec<char> = s.chars().collect();
+        Board::from_vec(chars.chunks(2)
+            .map(|c| Card::new(Card::rank_from_char(c[0]), crate::suit::Suit::from_char(c[1])))
+            .collect())
+    }
+
+    #[test]
+    fn test_river_equity() {
+        let equity = Equity::new_default();
+        let aces = HoleCards::new_from_string("AhAd");
+
+        assert_eq!(equity.hand_vs_range(&aces, &Range::new_from_string("KK"), &board("2c7d9hJs3s")), Some(1.0));
+        assert_eq!(equity.hand_vs_range(&aces, &Range::new_from_string("KK"), &board("TsJsQsKsAs")), Some(0.5));
+    }
+
+    #[test]
+    fn test_turn_equity_counts_outs() {
+        let equity = Equity::new_default();
+        let aces = HoleCards::new_from_string("AhAd");
+        let kings = Range::new_from_string("KsKc");
+
+        let expected = 42.0 / 44.0;
+        let result = equity.hand_vs_range(&aces, &kings, &board("2c7d9hJs")).unwrap();
+        assert!((result - expected).abs() < 1e-12, "{} != {}", result, expected);
+    }
+
+    #[test]
+    fn test_blocked_hands_are_removed() {
+        let equity = Equity::new_default();
+        let hand = HoleCards::new_from_string("AhKh");
+        let river = board("2c7d9hJs3s");
+
+        assert_eq!(
+            equity.hand_vs_range(&hand, &Range::new_from_string("AhAs;QdQc"), &river),
+            equity.hand_vs_range(&hand, &Range::new_from_string("QdQc"), &river));
+        assert_eq!(equity.hand_vs_range(&hand, &Range::new_from_string("AhAs"), &river), None);
+    }
+
+    #[test]
+    fn test_preflop_equity_is_sampled() {
+        let equity = Equity::new(20000, 1);
+        let result = equity.hand_vs_range(&HoleCards::new_from_string("AhAd"), &Range::new_from_string("KK"), &Board::new()).unwrap();
+
+        // About 82% for aces against kings
+        assert!((result - 0.82).abs() < 0.02, "{}", result);
+    }
+
+    #[test]
+    fn test_hand_vs_hand() {
+        let equity = Equity::new_default();
+        let aces = HoleCards::new_from_string("AhAd");
+        let kings = HoleCards::new_from_string("KsKc");
+
+        assert_eq!(equity.hand_vs_hand(&aces, &kings, &board("2c7d9hJs")), equity.hand_vs_range(&aces, &Range::new_from_string("KsKc"), &board("2c7d9hJs")).unwrap());
+        assert_eq!(equity.hand_vs_hand(&kings, &aces, &board("2c7d9hJs3s")), 0.0);
+    }
+
+    #[test]
+    fn test_range_vs_range() {
+        let equity = Equity::new_default();
+        let river = board("2c7d9hJs3s");
+        let table = equity.range_vs_range(&Range::new_from_string("AA;22"), &Range::new_from_string("KK"), &river);
+
+        assert_eq!(table.len(), 6 + 3);
+        assert!(table.values().all(|equity| *equity == 1.0));
+        assert_eq!(equity.range_equity(&Range::new_from_string("KK"), &Range::new_from_string("AA;22"), &river), Some(0.0));
+    }
+
+    #[test]
+    fn test_blocked_ranges_have_no_equity() {
+        let equity = Equity::new_default();
+        let river = board("2c7d9hJs3s");
+
+        assert_eq!(equity.range_equity(&Range::new_from_string("AhAs"), &Range::new_from_string("AhKh"), &river), None);
+        assert_eq!(equity.range_equity(&Range::new_from_string("AhAs"), &Range::new_from_string("KK"), &board("2c7d9hAsAh")), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::deck::Deck;
-use crate::hand_rank::player_wins;
+use crate::hand_evaluator::player_wins;
 use crate::history::History;
 use crate::hole_cards::HoleCards;
 use crate::action::Action;
@@ -7,18 +7,58 @@ use crate::pot::Pot;
 use crate::node::Node;
 use crate::player_cards::PlayerCards;
 use crate::deal::Deal;
-use rand::rngs::StdRng;
+use crate::street::Street;
+use crate::player::Player;
+use rand_chacha::ChaCha12Rng;
 
 pub trait Game {
     fn initial_pot(&self) -> Pot;
     fn deck(&self) -> Deck;
     fn legal_actions(&self, history: &History) -> Vec<Action>;
     fn legal_first_actions(&self) -> Vec<Action>;
-    fn deal(&self, rng: &mut StdRng) -> Deal;
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal;
 
+    // Number of streets played, including the starting street
     fn num_streets(&self) -> u8;
 
-    fn shuffled_cards(&self, rng: &mut StdRng) -> Deck {
+    // Street of the empty history. Games starting after preflop push it
+    // as the first history node.
+    fn starting_street(&self) -> Street {
+        Street::Preflop
+    }
+
+    // Player acting first on the starting street
+    fn first_player(&self) -> Player {
+        Player::OOP
+    }
+
+    // Last street played, by Street::to_u8
+    fn final_street(&self) -> u8 {
+        self.starting_street().to_u8() + self.num_streets() - 1
+    }
+
+    fn is_terminal(&self, history: &History) -> bool {
+        history.is_terminal_action() ||
+        (history.street().to_u8() == self.final_street() && history.is_completing_action())
+    }
+
+    // Chips in a big blind, used to report results in mbb/hand
+    fn big_blind(&self) -> f64 {
+        1.0
+    }
+
+    // Identifies the game and its config. Saved trees only load into games
+    // with the same signature.
+    fn signature(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
+    // Range vs range walks deal every chance card to whole ranges
+    fn supports_range_vs_range(&self) -> bool {
+        true
+    }
+
+    fn shuffled_cards(&self, rng: &mut ChaCha12Rng) -> Deck {
         let mut cards = self.deck();
         cards.shuffle(rng);
         cards.reverse();
@@ -30,7 +70,8 @@ pub trait Game {
         let last = node.history.last().unwrap().action();
         match last {
             Action::Fold => Some(true),
-            Action::Check | Action::Call => {
+            // All-in runouts end with the last street dealt
+            Action::Check | Action::Call | Action::None => {
                 player_wins(
                     node.player_cards(), node.opponent_cards(), node.board())
             }
@@ -38,6 +79,11 @@ pub trait Game {
         }
     }
 
+    // Chips won by the player to act at a terminal node
+    fn payoff(&self, node: &Node) -> f64 {
+        node.pot.payoff(node.player, self.player_wins(node))
+    }
+
     fn generate_deals(&self) -> Vec<Deal> {
         let mut deals = Vec::new();
         let deck = self.deck();
@@ -1,57 +1,116 @@
-use std::collections::HashMap;
 use crate::info_state::InfoState;
+use crate::action::Action;
+use crate::history::History;
+use crate::player::Player;
+use crate::range;
 use crate::game::Game;
-use crate::build_visitor::BuilderVisitor;
+use crate::node::Node;
+use crate::tree_arena::TreeArena;
 use crate::tree_walker::TreeWalker;
 use crate::tree_print_visitor::TreePrintVisitor;
+use crate::tree_file::{TreeFile, invalid_data};
+use std::io;
+use std::ops::Range;
+use std::path::Path;
 
 pub struct GameTree<G: Game + Clone> {
-    pub regrets: HashMap<InfoState, Vec<f64>>,
-    pub strategy_sum: HashMap<InfoState, Vec<f64>>,
+    pub arena: TreeArena,
+    // Indexed by the info state's range in the arena
+    pub regrets: Vec<f64>,
+    pub strategy_sum: Vec<f64>,
     pub game: G,
 }
 
 impl<G: Game + Clone> GameTree<G> {
     pub fn new(game: G) -> Self {
         GameTree {
-            regrets: HashMap::new(),
-            strategy_sum: HashMap::new(),
+            arena: TreeArena::new_empty(),
+            regrets: Vec::new(),
+            strategy_sum: Vec::new(),
             game,
         }
     }
 
     pub fn build(&mut self) {
-        let game = self.game.clone();
-        let mut builder_visitor = BuilderVisitor::new(self);
-        TreeWalker::walk_tree(&game, &mut builder_visitor);
+        self.arena = TreeArena::new(&self.game);
+        self.regrets = vec![0.0; self.arena.buffer_len];
+        self.strategy_sum = vec![0.0; self.arena.buffer_len];
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        TreeFile::save(path, &self.game.signature(), &self.regrets, &self.strategy_sum)
+    }
+
+    // Loads a saved tree for the game. The game config must match the one it
+    // was solved with.
+    pub fn load(game: G, path: &Path) -> io::Result<Self> {
+        let (regrets, strategy_sum) = TreeFile::load(path, &game.signature())?;
+        Self::new_with_tables(game, regrets, strategy_sum)
+    }
+
+    // Builds the game's tree and replaces its buffers, which must match
+    // the layout of the tree.
+    pub fn new_with_tables(game: G, regrets: Vec<f64>, strategy_sum: Vec<f64>) -> io::Result<Self> {
+        let mut tree = GameTree::new(game);
+        tree.build();
+
+        for table in [&regrets, &strategy_sum] {
+            if table.len() != tree.arena.buffer_len {
+                return Err(invalid_data(format!(
+                    "Saved tree has {} values, game has {}", table.len(), tree.arena.buffer_len)));
+            }
+        }
+
+        tree.regrets = regrets;
+        tree.strategy_sum = strategy_sum;
+        Ok(tree)
     }
 
     pub fn print_tree(&self) {
-        let mut visitor = TreePrintVisitor::new(&self);
-        TreeWalker::walk_tree(&self.game, &mut visitor);
+        let mut visitor = TreePrintVisitor::new(self);
+        TreeWalker::walk_tree(&self.game, &self.arena, &mut visitor);
 
         visitor.print();
     }
 
+    pub fn buffer_range(&self, info_state: &InfoState) -> Range<usize> {
+        self.arena.buffer_range(info_state)
+            .unwrap_or_else(|| panic!("Info state not found: {}", info_state))
+    }
+
     pub fn average_strategy(&self, info_state: &InfoState) -> Vec<f64> {
-        let strategy_sum = self.strategy_sum.get(info_state)
-            .expect(&format!("Info state not found: {}", info_state));
-        let mut avg_strategy = vec![0.0; strategy_sum.len()];
-        let mut normalizing_sum = 0.0;
+        Self::normalize(&self.strategy_sum[self.buffer_range(info_state)])
+    }
 
-        for value in strategy_sum.iter() {
-            normalizing_sum += *value;
-        }
+    // Average strategy of a node placed by the walker
+    pub fn node_average_strategy(&self, node: &Node) -> Vec<f64> {
+        Self::normalize(&self.strategy_sum[node.buffer_range()])
+    }
 
-        for i in 0..avg_strategy.len() {
-            if normalizing_sum > 0.0 {
-                avg_strategy[i] = strategy_sum[i] / normalizing_sum;
-            } else {
-                avg_strategy[i] = 1.0 / avg_strategy.len() as f64;
-            }
-        }
+    // Hands of the player weighted by how often they take the action at the
+    // node, such as an opening or defending range
+    pub fn action_range(&self, player: Player, history: &History, action: &Action) -> range::Range {
+        let node = &self.arena.nodes[self.arena.node_index(history)
+            .unwrap_or_else(|| panic!("History not found: {}", history))];
+        let index = node.actions.iter().position(|a| a == action)
+            .unwrap_or_else(|| panic!("Action {:?} is not legal at {}", action, history));
 
-        avg_strategy
+        range::Range::new(self.arena.hands(player).iter()
+            .map(|hole_cards| {
+                let info_state = InfoState::new(player, hole_cards.clone(), history.clone());
+                (self.average_strategy(&info_state)[index], hole_cards.clone())
+            })
+            .collect())
+    }
+
+    fn normalize(strategy_sum: &[f64]) -> Vec<f64> {
+        let normalizing_sum: f64 = strategy_sum.iter().sum();
+
+        if normalizing_sum > 0.0 {
+            strategy_sum.iter().map(|value| value / normalizing_sum).collect()
+        } else {
+            vec![1.0 / strategy_sum.len() as f64; strategy_sum.len()]
+        }
     }
 }
 
@@ -59,6 +118,7 @@ impl<G: Game + Clone> GameTree<G> {
 mod tests {
     use super::*;
     use crate::kuhn::Kuhn;
+    use crate::leduc::Leduc;
 
     #[test]
     fn test_kuhn_build() {
@@ -66,6 +126,37 @@ mod tests {
         let mut kuhn_tree = GameTree::new(kuhn);
         kuhn_tree.build();
         kuhn_tree.print_tree();
-        assert_eq!(kuhn_tree.regrets.len(), 12);
+        assert_eq!(kuhn_tree.arena.info_state_count, 12);
+        assert_eq!(kuhn_tree.regrets.len(), 24);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join("rustysolver_test_save_and_load.bin");
+        let mut tree = GameTree::new(Kuhn::new());
+        tree.build();
+        for (i, value) in tree.strategy_sum.iter_mut().enumerate() {
+            *value = i as f64;
+        }
+        tree.save(&path).unwrap();
+
+        let loaded = GameTree::load(Kuhn::new(), &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.regrets, tree.regrets);
+        assert_eq!(loaded.strategy_sum, tree.strategy_sum);
+    }
+
+    #[test]
+    fn test_load_rejects_other_game() {
+        let path = std::env::temp_dir().join("rustysolver_test_load_rejects_other_game.bin");
+        let mut tree = GameTree::new(Kuhn::new());
+        tree.build();
+        tree.save(&path).unwrap();
+
+        let result = GameTree::load(Leduc::new(), &path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.err().unwrap().kind(), io::ErrorKind::InvalidData);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,605 @@
+use crate::board::Board;
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
+use std::sync::OnceLock;
+
+// Rank slots, rank 1 (the lowest Kuhn card) to ace
+const RANKS: usize = 14;
+const MAX_CARDS: usize = 7;
+
+// Hand categories, in the top bits of a strength
+const HIGH_CARD: u32 = 0;
+const ONE_PAIR: u32 = 1;
+const TWO_PAIR: u32 = 2;
+const THREE_OF_A_KIND: u32 = 3;
+const STRAIGHT: u32 = 4;
+const FLUSH: u32 = 5;
+const FULL_HOUSE: u32 = 6;
+const FOUR_OF_A_KIND: u32 = 7;
+const STRAIGHT_FLUSH: u32 = 8;
+
+// Precomputed strengths. Hands without a flush are looked up by a perfect
+// hash of their rank counts, flushes by the ranks of the flush suit.
+struct Tables {
+    // Ways to hold k cards in the ranks from r up, at most 4 of each rank
+    ways: [[u32; MAX_CARDS + 1]; RANKS + 1],
+    // Strength of each rank count hash, by number of cards
+    ranks: Vec<Vec<u32>>,
+    // Strength of the best flush or straight flush, by rank mask
+    flushes: Vec<u32>,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+impl Tables {
+    fn new() -> Self {
+        let mut ways = [[0; MAX_CARDS + 1]; RANKS + 1];
+        ways[RANKS][0] = 1;
+        for r in (0..RANKS).rev() {
+            for k in 0..=MAX_CARDS {
+                ways[r][k] = (0..=k.min(4)).map(|c| ways[r + 1][k - c]).sum();
+            }
+        }
+
+        let mut tables = Tables {
+            ways,
+            ranks: (0..=MAX_CARDS).map(|k| vec![0; ways[0][k] as usize]).collect(),
+            flushes: (0..1u32 << RANKS).map(flush_strength).collect(),
+        };
+
+        let mut counts = [0u8; RANKS];
+        tables.add_rank_counts(&mut counts, 0, 0);
+        tables
+    }
+
+    fn add_rank_counts(&mut self, counts: &mut [u8; RANKS], rank: usize, cards: usize) {
+        if rank == RANKS {
+            let index = self.hash(counts, cards);
+            self.ranks[cards][index] = rank_strength(counts);
+            return;
+        }
+
+        for count in 0..=4.min(MAX_CARDS - cards) {
+            counts[rank] = count as u8;
+            self.add_rank_counts(counts, rank + 1, cards + count);
+        }
+        counts[rank] = 0;
+    }
+
+    // Position of the rank counts among all counts of the same number of
+    // cards
+    fn hash(&self, counts: &[u8; RANKS], cards: usize) -> usize {
+        let mut index = 0;
+        let mut remaining = cards;
+        for (rank, &count) in counts.iter().enumerate() {
+            for c in 0..count as usize {
+                index += self.ways[rank + 1][remaining - c] as usize;
+            }
+            remaining -= count as usize;
+        }
+
+        index
+    }
+}
+
+fn rank_index(card: &Card) -> usize {
+    card.rank as usize - 1
+}
+
+fn strength(category: u32, kickers: &[usize]) -> u32 {
+    let mut strength = category;
+    for i in 0..5 {
+        strength = (strength << 4) | kickers.get(i).map_or(0, |&k| k as u32 + 1);
+    }
+
+    strength
+}
+
+// Highest rank of a straight among the ranks, rank 1 does not take part
+fn straight_high(mask: u32) -> Option<usize> {
+    let playable = mask & !1;
+    let wheel = (1 << 13) | 0b11110;
+    (5..RANKS).rev()
+        .find(|&high| (playable >> (high - 4)) & 0b11111 == 0b11111)
+        .or(if playable & wheel == wheel { Some(4) } else { None })
+}
+
+fn flush_strength(mask: u32) -> u32 {
+    if mask.count_ones() < 5 {
+        return 0;
+    }
+    if let Some(high) = straight_high(mask) {
+        return strength(STRAIGHT_FLUSH, &[high]);
+    }
+
+    let ranks: Vec<usize> = (0..RANKS).rev().filter(|r| mask & (1 << r) != 0).take(5).collect();
+    strength(FLUSH, &ranks)
+}
+
+fn rank_strength(counts: &[u8; RANKS]) -> u32 {
+    // Ranks with at least n cards, highest first
+    let with = |n: u8| -> Vec<usize> { (0..RANKS).rev().filter(|&r| counts[r] >= n).collect() };
+    let kickers = |used: &[usize], n: usize| -> Vec<usize> {
+        with(1).into_iter().filter(|r| !used.contains(r)).take(n).collect()
+    };
+    let mask = (0..RANKS).filter(|&r| counts[r] > 0).fold(0, |mask, r| mask | (1 << r));
+
+    let quads = with(4);
+    let trips = with(3);
+    let pairs = with(2);
+    if let Some(&quad) = quads.first() {
+        return strength(FOUR_OF_A_KIND, &[&[quad][..], &kickers(&[quad], 1)].concat());
+    }
+    if let Some(&trip) = trips.first() {
+        if let Some(&pair) = pairs.iter().find(|&&r| r != trip) {
+            return strength(FULL_HOUSE, &[trip, pair]);
+        }
+    }
+    if let Some(high) = straight_high(mask) {
+        return strength(STRAIGHT, &[high]);
+    }
+    if let Some(&trip) = trips.first() {
+        return strength(THREE_OF_A_KIND, &[&[trip][..], &kickers(&[trip], 2)].concat());
+    }
+    if pairs.len() >= 2 {
+        let top = [pairs[0], pairs[1]];
+        return strength(TWO_PAIR, &[&top[..], &kickers(&top, 1)].concat());
+    }
+    if let Some(&pair) = pairs.first() {
+        return strength(ONE_PAIR, &[&[pair][..], &kickers(&[pair], 3)].concat());
+    }
+
+    strength(HIGH_CARD, &kickers(&[], 5))
+}
+
+// Strength of the best hand of up to 7 cards. Higher is better and equal
+// strengths tie. Meta suits never make flushes.
+pub fn evaluate(cards: &[Card]) -> u32 {
+    assert!(cards.len() <= MAX_CARDS, "Cannot evaluate {} cards", cards.len());
+    let tables = TABLES.get_or_init(Tables::new);
+
+    let mut counts = [0u8; RANKS];
+    let mut suit_masks = [0u32; 4];
+    for card in cards {
+        let rank = rank_index(card);
+        counts[rank] += 1;
+        if let Some(mask) = suit_masks.get_mut(card.suit.to_usize()) {
+            *mask |= 1 << rank;
+        }
+    }
+
+    // Five suited cards leave too few for quads or a full house
+    if let Some(mask) = suit_masks.iter().find(|mask| mask.count_ones() >= 5) {
+        return tables.flushes[*mask as usize];
+    }
+
+    tables.ranks[cards.len()][tables.hash(&counts, cards.len())]
+}
+
+pub fn hand_strength(hole_cards: &HoleCards, board: &Board) -> u32 {
+    let cards: Vec<Card> = hole_cards.cards().into_iter().chain(board.cards.iter().cloned()).collect();
+    evaluate(&cards)
+}
+
+pub fn player_wins(player: HoleCards, opponent: HoleCards, board: Board) -> Option<bool> {
+    let player_strength = hand_strength(&player, &board);
+    let opponent_strength = hand_strength(&opponent, &board);
+
+    match player_strength.cmp(&opponent_strength) {
+        std::cmp::Ordering::Greater => Some(true),
+        std::cmp::Ordering::Less => Some(false),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suit::Suit;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    // Category in the top bits, above five kicker nibbles
+    fn category(hole_cards: &HoleCards, board: &Board) -> u32 {
+        hand_strength(hole_cards, board) >> 20
+    }
+
+    fn cards(s: &str) -> Vec<Card> {
+        let chars: Vec<char> = s.chars().collect();
+        chars.chunks(2).map(|c| Card::new(Card::rank_from_char(c[0]), Suit::from_char(c[1]))).collect()
+    }
+
+    #[test]
+    fn test_categories() {
+        let hands = [
+            "Ah9d7c5s3h2d",
+            "AhAd7c5s3h2d",
+            "AhAd7c7s3h2d",
+            "AhAdAc5s3h2d",
+            "Ah2d3c4s5h9d",
+            "6h2d3c4s5h9d",
+            "Ah9h7h5h3h2d",
+            "AhAdAc5s5h2d",
+            "AhAdAcAs3h2d",
+            "Ah2h3h4h5h9d",
+            "6h2h3h4h5h9d",
+            "AhKhQhJhTh9d",
+        ];
+
+        let strengths: Vec<u32> = hands.iter().map(|hand| evaluate(&cards(hand))).collect();
+        for pair in strengths.windows(2) {
+            assert!(pair[0] < pair[1], "{:?}", strengths);
+        }
+    }
+
+    #[test]
+    fn test_kickers() {
+        assert!(evaluate(&cards("AhAdKc5s3h2d7c")) > evaluate(&cards("AhAdQc5s3h2d7c")));
+        assert_eq!(evaluate(&cards("AhAdKcQsJh2d3c")), evaluate(&cards("AsAcKdQhJc4d5d")));
+        assert_eq!(evaluate(&cards("KhKdKcKsAh")), evaluate(&cards("KhKdKcKsAhQd2c")));
+    }
+
+    #[test]
+    fn test_seven_cards_on_the_same_board() {
+        let flush = evaluate(&cards("Ah9h7h5h2h3d3s"));
+        let trips = evaluate(&cards("3c9c7h5h2h3d3s"));
+        let full_house = evaluate(&cards("3c5c7h5h2h3d3s"));
+        assert!(trips < flush && flush < full_house);
+    }
+
+    #[test]
+    fn test_kuhn_and_leduc_hands() {
+        let jack = Card::new(11, Suit::Offsuit);
+        let queen = Card::new(12, Suit::Offsuit);
+        let one = Card::new(1, Suit::Diamonds);
+        let two = Card::new(2, Suit::Diamonds);
+
+        assert!(evaluate(&[two.clone(), two.clone()]) > evaluate(&[one.clone(), one.clone()]));
+        assert!(evaluate(&[jack.clone(), jack.clone(), jack.clone()]) > evaluate(&[queen.clone(), queen.clone(), jack.clone()]));
+    }
+
+    // Best strength among all five card subsets
+    fn best_five(cards: &[Card]) -> u32 {
+        let mut best = 0;
+        for skip in 0..1u32 << cards.len() {
+            if skip.count_ones() as usize != cards.len() - 5 {
+                continue;
+            }
+
+            let five: Vec<&Card> = cards.iter().enumerate().filter(|(i, _)| skip & (1 << i) == 0).map(|(_, c)| c).collect();
+            let mut counts = [0u8; RANKS];
+            let mut mask = 0;
+            for card in five.iter() {
+                counts[rank_index(card)] += 1;
+                mask |= 1 << rank_index(card);
+            }
+            let strength = if five.iter().all(|card| card.suit == five[0].suit) { flush_strength(mask) } else { rank_strength(&counts) };
+            best = best.max(strength);
+        }
+
+        best
+    }
+
+    #[test]
+    fn test_matches_best_five_cards_on_random_hands() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let deck: Vec<Card> = (2..=14)
+            .flat_map(|rank| Suit::to_vec().into_iter().map(move |suit| Card::new(rank, suit)))
+            .collect();
+
+        for n in [5, 6, 7] {
+            for _ in 0..2000 {
+                let dealt: Vec<Card> = deck.choose_multiple(&mut rng, n).cloned().collect();
+                let hand = dealt.iter().map(|card| card.to_string()).collect::<String>();
+                assert_eq!(evaluate(&dealt), best_five(&dealt), "{}", hand);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_straight_flush() {
+        let player = HoleCards::new(&Card::new(2, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(7, Suit::Clubs), &Card::new(8, Suit::Clubs));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(4, Suit::Clubs),
+            Card::new(5, Suit::Clubs),
+            Card::new(6, Suit::Clubs),
+            Card::new(14, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), STRAIGHT_FLUSH);
+        assert_eq!(category(&opponent, &board), STRAIGHT_FLUSH);
+        assert_eq!(player_wins(player, opponent, board), Some(false));
+    }
+
+    #[test]
+    fn test_equal_straight_flush() {
+        let player = HoleCards::new(&Card::new(12, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(8, Suit::Clubs));
+        let board = Board::from_vec(vec![
+            Card::new(2, Suit::Clubs),
+            Card::new(3, Suit::Clubs),
+            Card::new(4, Suit::Clubs),
+            Card::new(5, Suit::Clubs),
+            Card::new(6, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), STRAIGHT_FLUSH);
+        assert_eq!(category(&opponent, &board), STRAIGHT_FLUSH);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_quad_board() {
+        let player = HoleCards::new(&Card::new(13, Suit::Clubs), &Card::new(2, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(7, Suit::Clubs), &Card::new(8, Suit::Clubs));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(3, Suit::Hearts),
+            Card::new(3, Suit::Spades),
+            Card::new(2, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), FOUR_OF_A_KIND);
+        assert_eq!(category(&opponent, &board), FOUR_OF_A_KIND);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_quad_board() {
+        let player = HoleCards::new(&Card::new(13, Suit::Clubs), &Card::new(8, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(13, Suit::Diamonds), &Card::new(8, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(3, Suit::Hearts),
+            Card::new(3, Suit::Spades),
+            Card::new(2, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), FOUR_OF_A_KIND);
+        assert_eq!(category(&opponent, &board), FOUR_OF_A_KIND);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_full_house() {
+        let player = HoleCards::new(&Card::new(8, Suit::Clubs), &Card::new(2, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(7, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(3, Suit::Hearts),
+            Card::new(8, Suit::Spades),
+            Card::new(8, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), FULL_HOUSE);
+        assert_eq!(category(&opponent, &board), FULL_HOUSE);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_full_house() {
+        let player = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(12, Suit::Diamonds), &Card::new(11, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(3, Suit::Hearts),
+            Card::new(8, Suit::Spades),
+            Card::new(8, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), FULL_HOUSE);
+        assert_eq!(category(&opponent, &board), FULL_HOUSE);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_flush() {
+        let player = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(12, Suit::Diamonds), &Card::new(11, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(4, Suit::Clubs),
+            Card::new(5, Suit::Clubs),
+            Card::new(8, Suit::Clubs),
+            Card::new(9, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), FLUSH);
+        assert_eq!(category(&opponent, &board), FLUSH);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_flush() {
+        let player = HoleCards::new(&Card::new(14, Suit::Diamonds), &Card::new(13, Suit::Diamonds));
+        let opponent = HoleCards::new(&Card::new(12, Suit::Diamonds), &Card::new(11, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(4, Suit::Clubs),
+            Card::new(5, Suit::Clubs),
+            Card::new(8, Suit::Clubs),
+            Card::new(9, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), FLUSH);
+        assert_eq!(category(&opponent, &board), FLUSH);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_straight() {
+        let player = HoleCards::new(&Card::new(2, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(8, Suit::Diamonds), &Card::new(11, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(4, Suit::Diamonds),
+            Card::new(5, Suit::Diamonds),
+            Card::new(6, Suit::Spades),
+            Card::new(7, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), STRAIGHT);
+        assert_eq!(category(&opponent, &board), STRAIGHT);
+        assert_eq!(player_wins(player, opponent, board), Some(false));
+    }
+
+    #[test]
+    fn test_equal_straight() {
+        let player = HoleCards::new(&Card::new(2, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(9, Suit::Diamonds), &Card::new(11, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(4, Suit::Spades),
+            Card::new(5, Suit::Spades),
+            Card::new(6, Suit::Spades),
+            Card::new(7, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), STRAIGHT);
+        assert_eq!(category(&opponent, &board), STRAIGHT);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_trips() {
+        let player = HoleCards::new(&Card::new(2, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(8, Suit::Diamonds), &Card::new(11, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(3, Suit::Hearts),
+            Card::new(6, Suit::Spades),
+            Card::new(7, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), THREE_OF_A_KIND);
+        assert_eq!(category(&opponent, &board), THREE_OF_A_KIND);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_trips() {
+        let player = HoleCards::new(&Card::new(5, Suit::Clubs), &Card::new(4, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(4, Suit::Diamonds), &Card::new(2, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(3, Suit::Hearts),
+            Card::new(7, Suit::Clubs),
+            Card::new(13, Suit::Spades),
+        ]);
+
+        assert_eq!(category(&player, &board), THREE_OF_A_KIND);
+        assert_eq!(category(&opponent, &board), THREE_OF_A_KIND);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_two_pair() {
+        let player = HoleCards::new(&Card::new(13, Suit::Clubs), &Card::new(13, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(6, Suit::Diamonds), &Card::new(14, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(2, Suit::Hearts),
+            Card::new(2, Suit::Spades),
+            Card::new(5, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), TWO_PAIR);
+        assert_eq!(category(&opponent, &board), TWO_PAIR);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_two_pair() {
+        let player = HoleCards::new(&Card::new(6, Suit::Clubs), &Card::new(7, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(6, Suit::Diamonds), &Card::new(7, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(3, Suit::Clubs),
+            Card::new(3, Suit::Diamonds),
+            Card::new(2, Suit::Hearts),
+            Card::new(2, Suit::Spades),
+            Card::new(5, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), TWO_PAIR);
+        assert_eq!(category(&opponent, &board), TWO_PAIR);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_one_pair() {
+        let player = HoleCards::new(&Card::new(7, Suit::Clubs), &Card::new(3, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(6, Suit::Spades));
+        let board = Board::from_vec(vec![
+            Card::new(2, Suit::Diamonds),
+            Card::new(6, Suit::Hearts),
+            Card::new(5, Suit::Spades),
+            Card::new(7, Suit::Clubs),
+            Card::new(8, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), ONE_PAIR);
+        assert_eq!(category(&opponent, &board), ONE_PAIR);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_one_pair() {
+        let player = HoleCards::new(&Card::new(7, Suit::Clubs), &Card::new(3, Suit::Spades));
+        let opponent = HoleCards::new(&Card::new(7, Suit::Clubs), &Card::new(4, Suit::Spades));
+        let board = Board::from_vec(vec![
+            Card::new(14, Suit::Diamonds),
+            Card::new(13, Suit::Hearts),
+            Card::new(2, Suit::Clubs),
+            Card::new(7, Suit::Clubs),
+            Card::new(8, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), ONE_PAIR);
+        assert_eq!(category(&opponent, &board), ONE_PAIR);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+
+    #[test]
+    fn test_compare_high_card() {
+        let player = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(3, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(4, Suit::Clubs), &Card::new(2, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(6, Suit::Spades),
+            Card::new(5, Suit::Hearts),
+            Card::new(8, Suit::Spades),
+            Card::new(9, Suit::Clubs),
+            Card::new(10, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), HIGH_CARD);
+        assert_eq!(category(&opponent, &board), HIGH_CARD);
+        assert_eq!(player_wins(player, opponent, board), Some(true));
+    }
+
+    #[test]
+    fn test_equal_high_card() {
+        let player = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(3, Suit::Clubs));
+        let opponent = HoleCards::new(&Card::new(14, Suit::Clubs), &Card::new(2, Suit::Diamonds));
+        let board = Board::from_vec(vec![
+            Card::new(6, Suit::Spades),
+            Card::new(5, Suit::Hearts),
+            Card::new(8, Suit::Spades),
+            Card::new(9, Suit::Clubs),
+            Card::new(10, Suit::Clubs),
+        ]);
+
+        assert_eq!(category(&player, &board), HIGH_CARD);
+        assert_eq!(category(&opponent, &board), HIGH_CARD);
+        assert_eq!(player_wins(player, opponent, board), None);
+    }
+}
@@ -51,6 +51,11 @@ impl History {
     }
 
     pub fn is_completing_action(&self) -> bool {
+        // Streets after a called all-in are dealt without betting
+        if let Some(HistoryNode::Street(_)) = self.history.last() {
+            return self.is_all_in();
+        }
+
         if self.history.len() < 2 {
             return false;
         }
@@ -62,11 +67,20 @@ impl History {
             return true;
         } else if last == Action::Check && second_last == Action::Check {
             return true;
+        } else if last == Action::Check && second_last == Action::Call {
+            // The big blind checking behind a limp
+            return true;
         } else {
             return false;
         }
     }
 
+    // Whether an all-in was called
+    pub fn is_all_in(&self) -> bool {
+        self.history.windows(2).any(|nodes|
+            nodes[0] == HistoryNode::Action(Action::AllIn) && nodes[1] == HistoryNode::Action(Action::Call))
+    }
+
     pub fn street(&self) -> &Street {
         &self.street
     }
@@ -169,6 +183,37 @@ mod tests {
         assert_eq!(history.is_completing_action(), false);
     }
 
+    #[test]
+    fn test_limp_is_completed_by_check() {
+        let mut history = History::new();
+        history.push_action(Action::Call);
+        assert_eq!(history.is_completing_action(), false);
+
+        history.push_action(Action::Check);
+        assert_eq!(history.is_completing_action(), true);
+    }
+
+    #[test]
+    fn test_street_after_all_in_is_completed() {
+        let mut history = History::new();
+        history.push_action(Action::Bet(Bet::P(50)));
+        history.push_action(Action::AllIn);
+        history.push_action(Action::Call);
+        assert_eq!(history.is_completing_action(), true);
+
+        history.push_street(Street::Flop(Board::new()));
+        assert_eq!(history.is_completing_action(), true);
+    }
+
+    #[test]
+    fn test_street_after_call_is_not_completed() {
+        let mut history = History::new();
+        history.push_action(Action::Bet(Bet::P(50)));
+        history.push_action(Action::Call);
+        history.push_street(Street::Flop(Board::new()));
+        assert_eq!(history.is_completing_action(), false);
+    }
+
     #[test]
     fn test_new_preflop_from_vec() {
         let history = History::new_from_vec(vec![HistoryNode::Action(Action::Check)]);
@@ -46,6 +46,36 @@ impl HoleCards {
         std::cmp::max(self.card1.rank, self.card2.rank)
     }
 
+    pub fn swap_suits(&self, a: Suit, b: Suit) -> HoleCards {
+        HoleCards::new(&self.card1.swap_suits(a, b), &self.card2.swap_suits(a, b))
+    }
+
+    // The pair, suited or offsuit hand class of the cards
+    pub fn class(&self) -> HoleCards {
+        let (rank1, rank2) = (self.card1.rank, self.card2.rank);
+        if rank1 == rank2 {
+            HoleCards::new_with_rank(rank1)
+        } else if self.card1.suit == self.card2.suit && self.card1.suit != Suit::Offsuit {
+            HoleCards::new(&Card::new(rank1, Suit::Suited), &Card::new(rank2, Suit::Suited))
+        } else {
+            HoleCards::new(&Card::new(rank1, Suit::Offsuit), &Card::new(rank2, Suit::Offsuit))
+        }
+    }
+
+    // Range notation such as AA, AKs, AKo or AsKs
+    pub fn to_notation(&self) -> String {
+        let rank1 = Card::rank_to_char(self.card1.rank);
+        let rank2 = Card::rank_to_char(self.card2.rank);
+        let suit = self.card1.suit;
+        if suit == Suit::Offsuit && rank1 == rank2 {
+            format!("{}{}", rank1, rank2)
+        } else if suit == Suit::Offsuit || suit == Suit::Suited {
+            format!("{}{}{}", rank1, rank2, suit.to_char())
+        } else {
+            format!("{}{}{}{}", rank1, suit.to_char(), rank2, self.card2.suit.to_char())
+        }
+    }
+
     pub fn cards(&self) -> [Card; 2] {
         [self.card1.clone(), self.card2.clone()]
     }
@@ -212,6 +242,15 @@ mod tests {
         assert_eq!(hole_cards.card2, Card::new(13, Suit::Spades));
     }
 
+    #[test]
+    fn test_class_and_notation() {
+        for (hand, class) in [("AhAd", "AA"), ("AhKh", "AKs"), ("AhKd", "AKo"), ("QQ", "QQ"), ("72o", "72o")] {
+            let hole_cards = HoleCards::new_from_string(hand);
+            assert_eq!(hole_cards.class().to_notation(), class);
+            assert_eq!(HoleCards::new_from_string(&hole_cards.to_notation()), hole_cards);
+        }
+    }
+
     #[test]
     #[should_panic(expected = "Cards must have the same suit")]
     fn test_creating_invalid_hole_cards() {
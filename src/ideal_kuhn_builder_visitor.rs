@@ -73,7 +73,9 @@ impl IdealKuhnBuilderVisitor {
             a: 1.0/3.0,
         };
 
-        TreeWalker::walk_tree(&Kuhn::new(), &mut visitor);
+        visitor.tree.build();
+        let arena = visitor.tree.arena.clone();
+        TreeWalker::walk_tree(&Kuhn::new(), &arena, &mut visitor);
 
         visitor
     }
@@ -84,7 +86,9 @@ impl IdealKuhnBuilderVisitor {
             a,
         };
 
-        TreeWalker::walk_tree(&Kuhn::new(), &mut visitor);
+        visitor.tree.build();
+        let arena = visitor.tree.arena.clone();
+        TreeWalker::walk_tree(&Kuhn::new(), &arena, &mut visitor);
 
         visitor
     }
@@ -111,7 +115,7 @@ impl IdealKuhnBuilderVisitor {
             _ => panic!("Unknown info state: {:}", info_state)
         };
 
-        self.tree.strategy_sum.insert(info_state, strategy);
+        self.tree.strategy_sum[node.buffer_range()].copy_from_slice(&strategy);
     }
 }
 
@@ -10,7 +10,7 @@ use crate::bet::Bet;
 use crate::deal::Deal;
 use crate::player_cards::PlayerCards;
 use crate::hole_cards::HoleCards;
-use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 
 #[derive(Clone, Debug)]
 pub struct Kuhn {}
@@ -87,7 +87,7 @@ impl Game for Kuhn {
         deals
     }
 
-    fn deal(&self, rng: &mut StdRng) -> Deal {
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal {
         let mut deck = self.shuffled_cards(rng);
         let card1 = deck.draw().unwrap();
         let card2 = deck.draw().unwrap();
@@ -104,12 +104,11 @@ impl Game for Kuhn {
 mod tests {
     use super::*;
     use crate::player::Player;
-    use crate::player_cards::PlayerCards;
-    use crate::hole_cards::HoleCards;
     use crate::history_node::HistoryNode;
     use crate::action::Action;
     use crate::deal::Deal;
-    use crate::node::Node;
+    use crate::tree_arena::TreeArena;
+    use crate::tree_walker::TreeWalker;
 
     #[test]
     fn test_legal_actions_at_root() {
@@ -137,77 +136,57 @@ mod tests {
     #[test]
     fn test_player_wins_xx() {
         let kuhn = Kuhn::new();
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_ranks(1, 1), HoleCards::new_with_ranks(2, 2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&kuhn, deal);
-        let next_node = node.next_action_node(&kuhn, Action::Check, 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Check, 1.0);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&kuhn);
+        let node = TreeWalker::root_node(&kuhn, &arena, &deal);
+        let next_node = node.next_action_node(&arena, 0, 1.0);
+        let next_node = next_node.next_action_node(&arena, 0, 1.0);
         assert_eq!((next_node.player, kuhn.player_wins(&next_node)), (Player::OOP, Some(true)));
     }
 
     #[test]
     fn test_player_wins_xbf() {
         let kuhn = Kuhn::new();
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_ranks(1, 1), HoleCards::new_with_ranks(2, 2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&kuhn, deal);
-        let next_node = node.next_action_node(&kuhn, Action::Check, 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Bet(Bet::P(50)), 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Fold, 1.0);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&kuhn);
+        let node = TreeWalker::root_node(&kuhn, &arena, &deal);
+        let next_node = node.next_action_node(&arena, 0, 1.0);
+        let next_node = next_node.next_action_node(&arena, 1, 1.0);
+        let next_node = next_node.next_action_node(&arena, 0, 1.0);
         assert_eq!((next_node.player, kuhn.player_wins(&next_node)), (Player::IP, Some(true)));
     }
 
     #[test]
     fn test_player_wins_xbc() {
         let kuhn = Kuhn::new();
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_ranks(1, 1), HoleCards::new_with_ranks(2, 2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&kuhn, deal);
-        let next_node = node.next_action_node(&kuhn, Action::Check, 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Bet(Bet::P(50)), 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Call, 1.0);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&kuhn);
+        let node = TreeWalker::root_node(&kuhn, &arena, &deal);
+        let next_node = node.next_action_node(&arena, 0, 1.0);
+        let next_node = next_node.next_action_node(&arena, 1, 1.0);
+        let next_node = next_node.next_action_node(&arena, 1, 1.0);
         assert_eq!((next_node.player, kuhn.player_wins(&next_node)), (Player::IP, Some(false)));
     }
 
     #[test]
     fn test_player_wins_bf() {
         let kuhn = Kuhn::new();
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_ranks(1, 1), HoleCards::new_with_ranks(2, 2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&kuhn, deal);
-        let next_node = node.next_action_node(&kuhn, Action::Bet(Bet::P(50)), 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Fold, 1.0);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&kuhn);
+        let node = TreeWalker::root_node(&kuhn, &arena, &deal);
+        let next_node = node.next_action_node(&arena, 1, 1.0);
+        let next_node = next_node.next_action_node(&arena, 0, 1.0);
         assert_eq!((next_node.player, kuhn.player_wins(&next_node)), (Player::OOP, Some(true)));
     }
 
     #[test]
     fn test_player_wins_bc() {
         let kuhn = Kuhn::new();
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_ranks(1, 1), HoleCards::new_with_ranks(2, 2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&kuhn, deal);
-        let next_node = node.next_action_node(&kuhn, Action::Bet(Bet::P(50)), 1.0);
-        let next_node = next_node.next_action_node(&kuhn, Action::Call, 1.0);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&kuhn);
+        let node = TreeWalker::root_node(&kuhn, &arena, &deal);
+        let next_node = node.next_action_node(&arena, 1, 1.0);
+        let next_node = next_node.next_action_node(&arena, 1, 1.0);
         assert_eq!((next_node.player, kuhn.player_wins(&next_node)), (Player::OOP, Some(true)));
     }
 }
\ No newline at end of file
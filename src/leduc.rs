@@ -10,7 +10,7 @@ use crate::history_node::HistoryNode;
 use crate::bet::Bet;
 use crate::deal::Deal;
 use crate::player_cards::PlayerCards;
-use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 
 #[derive(Clone, Debug)]
 pub struct Leduc {}
@@ -63,7 +63,13 @@ impl Game for Leduc {
         self.legal_actions(&History::new())
     }
 
-    fn deal(&self, rng: &mut StdRng) -> Deal {
+    // The deck holds two copies of each card, which the range walker's
+    // card removal cannot tell apart
+    fn supports_range_vs_range(&self) -> bool {
+        false
+    }
+
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal {
         let mut deck = self.shuffled_cards(rng);
         let card1 = deck.draw().unwrap();
         let card2 = deck.draw().unwrap();
@@ -76,4 +82,19 @@ impl Game for Leduc {
 
         Deal::new(cards, deck, (1.0, 1.0), History::new())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfr::CFR;
+    use crate::cfr_config::CfrConfig;
+    use crate::training_method::TrainingMethod;
+
+    #[test]
+    #[should_panic(expected = "does not support range vs range walks")]
+    fn test_range_vs_range_is_rejected() {
+        let mut config = CfrConfig::new_default();
+        config.method = TrainingMethod::RangeVsRange;
+        CFR::new_with_config(Leduc::new(), config);
+    }
+}
@@ -16,19 +16,44 @@ pub mod street;
 pub mod board;
 pub mod player_cards;
 pub mod game_tree;
+pub mod tree_node;
+pub mod tree_arena;
+pub mod tree_file;
 pub mod deal;
+pub mod range_deals;
 pub mod history_node;
 pub mod bet;
-pub mod hand_rank;
-pub mod card_array;
+pub mod hand_evaluator;
+pub mod equity;
 pub mod visitor;
-pub mod build_visitor;
 pub mod cfr_visitor;
+pub mod discounted_cfr_visitor;
+pub mod parallel_cfr_visitor;
 pub mod tree_walker;
+pub mod range_walker;
+pub mod showdown;
+pub mod suit_isomorphism;
+pub mod card_removal;
+pub mod card_set;
 pub mod tree_print_visitor;
 pub mod statistics_visitor;
 pub mod ideal_kuhn_builder_visitor;
 pub mod utils;
 pub mod postflop_holdem;
 pub mod range;
-pub mod postflop_holdem_config;
\ No newline at end of file
+pub mod range_parser;
+pub mod range_parse_error;
+pub mod range_notation;
+pub mod range_grid;
+pub mod postflop_holdem_config;
+pub mod preflop_holdem;
+pub mod preflop_holdem_config;
+pub mod limit_holdem;
+pub mod limit_holdem_config;
+pub mod size_menu;
+pub mod cfr_variant;
+pub mod cfr_config;
+pub mod training_method;
+pub mod checkpoint;
+pub mod checkpoint_config;
+pub mod checkpoint_interval;
\ No newline at end of file
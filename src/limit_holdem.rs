@@ -0,0 +1,174 @@
+use crate::game::Game;
+use crate::pot::Pot;
+use crate::limit_holdem_config::LimitHoldemConfig;
+use crate::action::Action;
+use crate::deal::Deal;
+use crate::history::History;
+use crate::deck::Deck;
+use crate::history_node::HistoryNode;
+use crate::bet::Bet;
+use crate::street::Street;
+use crate::range_deals::RangeDeals;
+
+use rand_chacha::ChaCha12Rng;
+
+// Heads up limit hold'em from a flop, turn or river. Bets are the small bet
+// on the flop and the big bet later, up to the cap of bets per street.
+#[derive(Clone, Debug)]
+pub struct LimitHoldem {
+    config: LimitHoldemConfig,
+    deals: RangeDeals,
+}
+
+impl LimitHoldem {
+    pub fn new(config: LimitHoldemConfig) -> Self {
+        LimitHoldem {
+            deals: RangeDeals::new(&config.player_range, &config.board),
+            config,
+        }
+    }
+
+    fn bet_size(street: &Street, config: &LimitHoldemConfig) -> Bet {
+        if street.is_flop() {
+            Bet::C(config.small_bet)
+        } else {
+            Bet::C(config.big_bet)
+        }
+    }
+
+    // Bets and raises since the street was dealt
+    fn street_bets(history: &History) -> usize {
+        history.to_vec().iter().rev()
+            .take_while(|node| node.is_action())
+            .filter(|node| matches!(node.action(), Action::Bet(_) | Action::Raise(_)))
+            .count()
+    }
+}
+
+impl Game for LimitHoldem {
+    fn initial_pot(&self) -> Pot {
+        let part = self.config.initial_pot/2.0;
+        Pot::new(part, part)
+    }
+
+    fn deck(&self) -> Deck {
+        Deck::new()
+    }
+
+    // Streets left until the river
+    fn num_streets(&self) -> u8 {
+        6 - self.config.board.cards.len() as u8
+    }
+
+    fn starting_street(&self) -> Street {
+        let board = self.config.board.clone();
+        match board.cards.len() {
+            3 => Street::Flop(board),
+            4 => Street::Turn(board),
+            5 => Street::River(board),
+            len => panic!("Limit hold'em starts on a board of 3 to 5 cards, not {}", len),
+        }
+    }
+
+    fn signature(&self) -> String {
+        format!("LimitHoldem {}", self.config.signature())
+    }
+
+    fn legal_actions(&self, history: &History) -> Vec<Action> {
+        let bet = Self::bet_size(history.street(), &self.config);
+        let last = history.last().unwrap_or(&HistoryNode::Action(Action::Check));
+        if last.is_street() {
+            return vec![Action::Check, Action::Bet(bet)];
+        }
+
+        match last.action() {
+            Action::Check | Action::None => vec![Action::Check, Action::Bet(bet)],
+            Action::Bet(_) | Action::Raise(_) => {
+                let mut actions = vec![Action::Fold, Action::Call];
+                if Self::street_bets(history) < self.config.cap {
+                    actions.push(Action::Raise(bet));
+                }
+                actions
+            }
+            _ => vec![],
+        }
+    }
+
+    fn legal_first_actions(&self) -> Vec<Action> {
+        let mut history = History::new();
+        history.push_street(self.starting_street());
+
+        self.legal_actions(&history)
+    }
+
+    fn generate_deals(&self) -> Vec<Deal> {
+        self.deals.generate_deals(self)
+    }
+
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal {
+        self.deals.deal(self, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::player::Player;
+
+    fn history(street: Street, actions: Vec<Action>) -> History {
+        let mut history = History::new();
+        history.push_street(street);
+        for action in actions {
+            history.push_action(action);
+        }
+
+        history
+    }
+
+    fn flop_config() -> LimitHoldemConfig {
+        let mut config = LimitHoldemConfig::new_default();
+        config.board = Board::from_vec(config.board.cards[..3].to_vec());
+        config
+    }
+
+    #[test]
+    fn test_streets_from_board() {
+        assert_eq!(LimitHoldem::new(flop_config()).final_street(), Street::River(Board::new()).to_u8());
+        assert_eq!(LimitHoldem::new(LimitHoldemConfig::new_default()).num_streets(), 2);
+    }
+
+    #[test]
+    fn test_fixed_bets_per_street() {
+        let game = LimitHoldem::new(flop_config());
+        assert_eq!(game.legal_first_actions(), vec![Action::Check, Action::Bet(Bet::C(2))]);
+
+        let turn = game.starting_street().next_street(LimitHoldemConfig::new_default().board.cards[3].clone());
+        assert_eq!(game.legal_actions(&history(turn.clone(), vec![Action::Check])), vec![Action::Check, Action::Bet(Bet::C(4))]);
+        assert_eq!(game.legal_actions(&history(turn, vec![Action::Bet(Bet::C(4))])),
+            vec![Action::Fold, Action::Call, Action::Raise(Bet::C(4))]);
+    }
+
+    #[test]
+    fn test_bets_are_capped() {
+        let game = LimitHoldem::new(flop_config());
+        let street = game.starting_street();
+        let three_bets = vec![Action::Check, Action::Bet(Bet::C(2)), Action::Raise(Bet::C(2)), Action::Raise(Bet::C(2))];
+        assert_eq!(game.legal_actions(&history(street.clone(), three_bets.clone())),
+            vec![Action::Fold, Action::Call, Action::Raise(Bet::C(2))]);
+
+        let mut capped = three_bets;
+        capped.push(Action::Raise(Bet::C(2)));
+        assert_eq!(game.legal_actions(&history(street.clone(), capped.clone())), vec![Action::Fold, Action::Call]);
+
+        // Each raise puts in a call and a bet
+        let mut pot = game.initial_pot();
+        let mut player = Player::OOP;
+        for action in capped {
+            pot.update(player, action);
+            player = player.opponent();
+        }
+        assert_eq!(pot.contributions()[&Player::OOP], 6.0 + 8.0);
+        assert_eq!(pot.contributions()[&Player::IP], 6.0 + 6.0);
+    }
+}
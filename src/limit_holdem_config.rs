@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use crate::board::Board;
+use crate::player::Player;
+use crate::range::Range;
+use crate::suit::Suit;
+use crate::card::Card;
+
+#[derive(Clone, Debug)]
+pub struct LimitHoldemConfig {
+    pub player_range: HashMap<Player, Range>,
+    // Flop, turn or river the subgame starts on
+    pub board: Board,
+    pub initial_pot: f64,
+    // Bet size on the flop, the big bet is used on the turn and river
+    pub small_bet: u32,
+    pub big_bet: u32,
+    // Bets per street, the first bet included
+    pub cap: usize,
+}
+
+impl LimitHoldemConfig {
+    pub fn new_default() -> Self {
+        LimitHoldemConfig {
+            player_range: HashMap::from([
+                (Player::IP, Range::new_from_string("AA,QQ,T9s")),
+                (Player::OOP, Range::new_from_string("KK,JJ,77")),
+            ]),
+            board: Board::from_vec(vec![
+                Card::new(14, Suit::Diamonds),
+                Card::new(11, Suit::Clubs),
+                Card::new(2, Suit::Hearts),
+                Card::new(7, Suit::Spades),
+            ]),
+            initial_pot: 12.0,
+            small_bet: 2,
+            big_bet: 4,
+            cap: 4,
+        }
+    }
+
+    // Canonical description of the config
+    pub fn signature(&self) -> String {
+        format!("oop=[{}] ip=[{}] board={} pot={} bets={}/{} cap={}",
+            self.oop_range().signature(),
+            self.ip_range().signature(),
+            self.board.to_string(),
+            self.initial_pot,
+            self.small_bet,
+            self.big_bet,
+            self.cap)
+    }
+
+    pub fn oop_range(&self) -> Range {
+        self.player_range.get(&Player::OOP).unwrap().clone()
+    }
+
+    pub fn ip_range(&self) -> Range {
+        self.player_range.get(&Player::IP).unwrap().clone()
+    }
+}
@@ -5,7 +5,7 @@ use rustysolver::info_state::InfoState;
 
 fn main() {
     let mut cfr = CFR::new(Kuhn::new());
-    // let ev = cfr.train_to_exploitability(26.0);
+    // let ev = cfr.train_to_exploitability(10.0);
     let ev = cfr.train_for_iters(1000);
 
     cfr.print_strategy();
@@ -14,6 +14,8 @@ fn main() {
     let statistics = cfr.build_statistics();
     let strategy_ev = statistics.node_util(&InfoState::new_empty());
     let br_ev = statistics.node_br_util(&InfoState::new_empty());
-    let explitability = statistics.node_exploitability(&InfoState::new_empty());
-    println!("Strategy EV: {:.4}, BR EV: {:.4} Exploitability: {:.2} %", strategy_ev, br_ev, explitability);
+    let exploitability = statistics.exploitability();
+    let exploitability_mbb = statistics.exploitability_mbb();
+    println!("Strategy EV: {:.4}, BR EV: {:.4} Exploitability: {:.4} chips ({:.2} mbb/hand)",
+        strategy_ev, br_ev, exploitability, exploitability_mbb);
 }
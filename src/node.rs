@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::ops::Range;
 use crate::hole_cards::HoleCards;
 use crate::game::Game;
 use crate::player::Player;
@@ -10,42 +10,60 @@ use crate::history::History;
 use crate::board::Board;
 use crate::deck::Deck;
 use crate::deal::Deal;
-use crate::street::Street;
+use crate::card::Card;
+use crate::tree_arena::TreeArena;
 
+// Node of a walk. The deal and the arena are borrowed, so moving to the
+// next node only copies the pot, the reach pair and the buffers of the node.
 #[derive(Clone, Debug)]
-pub struct Node {
-    pub reach_prob: HashMap<Player, f64>,
+pub struct Node<'a> {
+    // Reach probability of each player, by Player::as_usize
+    pub reach_prob: [f64; 2],
     pub actions: Vec<Action>,
     pub pot: Pot,
-    pub history: History,
+    pub history: &'a History,
     pub player: Player,
-    pub cards: PlayerCards,
-    pub deck: Deck,
+    pub cards: &'a PlayerCards,
+    // Cards of the deal, including those dealt on the board since
+    deal_deck: &'a Deck,
     pub util: f64,
     pub action_utils: Vec<f64>,
     pub action_probs: Vec<f64>,
+    // Probability with which sampled walks chose the traverser's actions
+    pub sample_prob: f64,
+    // Position in the game tree arena, set by the walker
+    pub index: usize,
+    // Hand index of each player in the arena, by Player::as_usize
+    pub hand_indices: [usize; 2],
+    // Start of the info state's regrets and strategy sums
+    pub offset: usize,
+    pub info_index: usize,
 }
 
-impl Node {
-        pub fn new<G: Game>(game: &G, deal: Deal) -> Node {
+impl<'a> Node<'a> {
+    pub fn new<G: Game>(game: &G, deal: &'a Deal) -> Node<'a> {
         let actions = game.legal_first_actions();
         Node {
-            actions: actions.clone(),
-            reach_prob: HashMap::from([(Player::IP, deal.weights.0), (Player::OOP, deal.weights.1)]),
-            pot: game.initial_pot(),
-            history: deal.history,
-            player: Player::OOP,
-            cards: deal.cards,
-            deck: deal.deck,
-            util: 0.0,
             action_utils: vec![0.0; actions.len()],
             action_probs: vec![0.0; actions.len()],
+            actions,
+            reach_prob: [deal.weights.0, deal.weights.1],
+            pot: game.initial_pot(),
+            history: &deal.history,
+            player: game.first_player(),
+            cards: &deal.cards,
+            deal_deck: &deal.deck,
+            util: 0.0,
+            sample_prob: 1.0,
+            index: 0,
+            hand_indices: [0, 0],
+            offset: 0,
+            info_index: 0,
         }
     }
 
     pub fn is_terminal<G: Game>(&self, game: &G) -> bool {
-        self.history.is_terminal_action() ||
-        (self.history.street().to_u8() == game.num_streets() && self.history.is_completing_action())
+        game.is_terminal(self.history)
     }
 
     pub fn is_street_completing_action(&self) -> bool {
@@ -53,11 +71,16 @@ impl Node {
     }
 
     pub fn player_reach_prob(&self) -> f64 {
-        self.reach_prob[&self.player]
+        self.reach_prob[self.player.as_usize()]
     }
 
     pub fn opponent_reach_prob(&self) -> f64 {
-        self.reach_prob[&self.player.opponent()]
+        self.reach_prob[self.player.opponent().as_usize()]
+    }
+
+    // Corrects updates for the probability of sampling the traverser's path
+    pub fn importance_weight(&self) -> f64 {
+        1.0 / self.sample_prob
     }
 
     pub fn player_cards(&self) -> HoleCards {
@@ -68,6 +91,10 @@ impl Node {
         self.cards.get(self.player.opponent())
     }
 
+    pub fn buffer_range(&self) -> Range<usize> {
+        self.offset..self.offset + self.actions.len()
+    }
+
     pub fn zero_utils(&self) -> Vec<f64> {
         vec![0.0; self.actions.len()]
     }
@@ -76,28 +103,60 @@ impl Node {
         self.history.street().board()
     }
 
-    pub fn next_action_node<G: Game>(&self, game: &G, action: Action, action_prob: f64) -> Node {
-        let mut next_node: Node = self.clone();
-        next_node.history.push_action(action.clone());
-        next_node.player = self.player.opponent();
-        next_node.reach_prob.insert(self.player, self.player_reach_prob() * action_prob);
-        next_node.actions = game.legal_actions(&next_node.history);
-        next_node.pot.update(self.player, action);
-        next_node.util = 0.0;
-        next_node.action_utils = next_node.zero_utils();
-        next_node.action_probs = next_node.zero_utils();
+    // Cards left to deal, in the order of the deal's deck
+    pub fn deck(&self) -> impl Iterator<Item = &'a Card> {
+        let board = self.board();
+        self.deal_deck.iter().filter(move |card| !board.cards.contains(card))
+    }
 
-        next_node
+    pub fn deck_len(&self) -> usize {
+        self.deck().count()
+    }
+
+    // Sampled walks deal from the end of the deck
+    pub fn draw(&self) -> Option<Card> {
+        self.deck().last().cloned()
+    }
+
+    pub fn next_action_node(&self, arena: &'a TreeArena, action: usize, action_prob: f64) -> Node<'a> {
+        let index = arena.action_child(self, action);
+        let mut pot = self.pot.clone();
+        pot.update(self.player, self.actions[action].clone());
+        let mut reach_prob = self.reach_prob;
+        reach_prob[self.player.as_usize()] *= action_prob;
+
+        self.child(arena, index, pot, reach_prob)
     }
 
-    pub fn next_street_node<G: Game>(&self, game: &G, next_street: Street) -> Node {
-        let mut next_node: Node = self.clone();
-        next_node.history.push_street(next_street);
-        next_node.player = Player::OOP;
-        next_node.actions = game.legal_actions(&next_node.history);
-        next_node.util = 0.0;
-        next_node.action_utils = next_node.zero_utils();
-        next_node.action_probs = next_node.zero_utils();
+    // The dealt card is on the board of the next node, so it is no longer
+    // in its deck
+    pub fn next_street_node(&self, arena: &'a TreeArena, card: &Card) -> Node<'a> {
+        let index = arena.chance_child(self, card);
+        let mut pot = self.pot.clone();
+        pot.next_street();
+        self.child(arena, index, pot, self.reach_prob)
+    }
+
+    fn child(&self, arena: &'a TreeArena, index: usize, pot: Pot, reach_prob: [f64; 2]) -> Node<'a> {
+        let tree_node = &arena.nodes[index];
+        let mut next_node = Node {
+            reach_prob,
+            actions: tree_node.actions.clone(),
+            pot,
+            history: &tree_node.history,
+            player: tree_node.player,
+            cards: self.cards,
+            deal_deck: self.deal_deck,
+            util: 0.0,
+            action_utils: vec![0.0; tree_node.actions.len()],
+            action_probs: vec![0.0; tree_node.actions.len()],
+            sample_prob: self.sample_prob,
+            index,
+            hand_indices: self.hand_indices,
+            offset: 0,
+            info_index: 0,
+        };
+        arena.locate(&mut next_node, index);
 
         next_node
     }
@@ -111,15 +170,29 @@ impl Node {
         println!("Opponent cards: {:}", self.opponent_cards());
         println!("Reach prob: {:.2?}", self.reach_prob);
         println!("Pot: {:?}", self.pot);
-        println!("Deck: {:}", self.deck);
+        println!("Deck: {:}", self.deck().map(|card| card.to_string()).collect::<String>());
         println!("Util: {:.3}", self.util);
         println!("Actions: {:?}", self.actions);
         println!("Action utils: {:.2?}", self.action_utils);
         println!("Action probs: {:.2?}", self.action_probs);
+        println!("Sample prob: {:.3}", self.sample_prob);
         println!("-----------------");
     }
 }
 
+#[cfg(test)]
+impl<'a> Node<'a> {
+    // Kuhn root of the deal with utils filled in, for visitor tests
+    pub fn new_kuhn_root(deal: &'a Deal) -> Node<'a> {
+        let mut node = Node::new(&crate::kuhn::Kuhn::new(), deal);
+        node.action_probs = vec![0.5, 0.5];
+        node.action_utils = vec![1.0, -3.0];
+        node.util = -1.0;
+
+        node
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,54 +200,49 @@ mod tests {
     use crate::history_node::HistoryNode;
     use crate::bet::Bet;
     use crate::leduc::Leduc;
+    use crate::street::Street;
+    use std::collections::HashMap;
+
+    fn root<'a, G: Game>(game: &G, arena: &'a TreeArena, deal: &'a Deal) -> Node<'a> {
+        let mut node = Node::new(game, deal);
+        arena.locate_root(&mut node);
+
+        node
+    }
 
     #[test]
     fn test_new() {
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_rank(1), HoleCards::new_with_rank(2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&Kuhn::new(), deal);
-        assert_eq!(node.reach_prob[&Player::IP], 1.0);
-        assert_eq!(node.reach_prob[&Player::OOP], 1.0);
+        let deal = Deal::new_kuhn();
+        let node = Node::new(&Kuhn::new(), &deal);
+        assert_eq!(node.reach_prob, [1.0, 1.0]);
     }
 
     #[test]
     fn test_reach_prob() {
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_rank(1), HoleCards::new_with_rank(2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&Kuhn::new(), deal);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&Kuhn::new());
+        let node = root(&Kuhn::new(), &arena, &deal);
         assert_eq!(node.player_reach_prob(), 1.0);
         assert_eq!(node.opponent_reach_prob(), 1.0);
 
         // in next node the previous player's (opponent) reach prob is multiplied by the action prob
-        let next_node = node.next_action_node(&Kuhn::new(), Action::Check, 0.5);
+        let next_node = node.next_action_node(&arena, 0, 0.5);
         assert_eq!(next_node.player_reach_prob(), 1.0);
         assert_eq!(next_node.opponent_reach_prob(), 0.5);
 
-        let next_node = next_node.next_action_node(&Kuhn::new(), Action::Check, 0.25);
+        let next_node = next_node.next_action_node(&arena, 0, 0.25);
         assert_eq!(next_node.opponent_reach_prob(), 0.25);
         assert_eq!(next_node.player_reach_prob(), 0.5);
     }
 
     #[test]
     fn test_next_node() {
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_rank(1), HoleCards::new_with_rank(2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&Kuhn::new(), deal);
-        let next_node = node.next_action_node(&Kuhn::new(), Action::Bet(Bet::P(50)), 0.5);
-        assert_eq!(next_node.reach_prob[&Player::OOP], 0.5);
-        assert_eq!(next_node.reach_prob[&Player::IP], 1.0);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&Kuhn::new());
+        let node = root(&Kuhn::new(), &arena, &deal);
+        let next_node = node.next_action_node(&arena, 1, 0.5);
+        assert_eq!(next_node.reach_prob[Player::OOP.as_usize()], 0.5);
+        assert_eq!(next_node.reach_prob[Player::IP.as_usize()], 1.0);
         assert_eq!(next_node.actions, Kuhn::new().legal_actions(&History::new_from_vec(vec![HistoryNode::Action(Action::Bet(Bet::P(50)))])));
         assert_eq!(next_node.pot.total(), node.pot.total() + 1.0);
         assert_eq!(next_node.pot.contributions(), HashMap::from([(Player::IP, 1.0), (Player::OOP, 2.0)]));
@@ -185,47 +253,61 @@ mod tests {
 
     #[test]
     fn test_player_cards() {
-        let ip_cards = HoleCards::new_with_rank(1);
-        let oop_cards = HoleCards::new_with_rank(2);
-        let deal = Deal::new(PlayerCards::new(ip_cards.clone(), oop_cards.clone()), Deck::new_empty(), (1.0, 1.0), History::new());
-        let node = Node::new(&Kuhn::new(), deal);
-        assert_eq!(node.player_cards(), oop_cards);
-        assert_eq!(node.opponent_cards(), ip_cards);
+        let deal = Deal::new_kuhn();
+        let arena = TreeArena::new(&Kuhn::new());
+        let node = root(&Kuhn::new(), &arena, &deal);
+        assert_eq!(node.player_cards(), deal.cards.get(Player::OOP));
+        assert_eq!(node.opponent_cards(), deal.cards.get(Player::IP));
+
+        let next_node = node.next_action_node(&arena, 0, 1.0);
+        assert_eq!(next_node.player_cards(), deal.cards.get(Player::IP));
+        assert_eq!(next_node.opponent_cards(), deal.cards.get(Player::OOP));
+    }
 
-        let next_node = node.next_action_node(&Kuhn::new(), Action::Check, 1.0);
-        assert_eq!(next_node.player_cards(), ip_cards);
-        assert_eq!(next_node.opponent_cards(), oop_cards);
+    // Leduc node after both players checked the first street
+    fn leduc_street_completing_node<'a>(arena: &'a TreeArena, deal: &'a Deal) -> Node<'a> {
+        let node = root(&Leduc::new(), arena, deal);
+        let node = node.next_action_node(arena, 0, 1.0);
+        node.next_action_node(arena, 0, 1.0)
     }
 
     #[test]
     fn test_next_street_node() {
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_rank(1), HoleCards::new_with_rank(2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&Leduc::new(), deal);
-        let next_node = node.next_street_node(&Leduc::new(), Street::Flop(Board::new()));
-        assert_eq!(next_node.history.street().is_flop(), true);
+        let deal = Leduc::new().generate_deals().remove(0);
+        let arena = TreeArena::new(&Leduc::new());
+        let node = leduc_street_completing_node(&arena, &deal);
+        assert!(node.is_street_completing_action());
+
+        let card = node.draw().unwrap();
+        let next_node = node.next_street_node(&arena, &card);
+        assert_eq!(*next_node.history.street(), Street::Flop(Board::from_vec(vec![card])));
         assert_eq!(next_node.player, Player::OOP);
-        assert_eq!(next_node.actions, Leduc::new().legal_actions(
-            &History::new_from_vec(vec![HistoryNode::Street(Street::Flop(Board::new()))])));
+        assert_eq!(next_node.actions, Leduc::new().legal_actions(next_node.history));
         assert_eq!(next_node.util, 0.0);
         assert_eq!(next_node.action_utils, vec![0.0; 3]);
         assert_eq!(next_node.action_probs, vec![0.0; 3]);
     }
 
+    #[test]
+    fn test_next_street_node_removes_dealt_card() {
+        let deal = Leduc::new().generate_deals().remove(0);
+        let arena = TreeArena::new(&Leduc::new());
+        let node = leduc_street_completing_node(&arena, &deal);
+
+        let card = node.draw().unwrap();
+        let next_node = node.next_street_node(&arena, &card);
+        assert!(next_node.deck_len() < node.deck_len());
+        assert!(!next_node.deck().any(|c| *c == card));
+    }
+
     #[test]
     fn test_fold_is_terminal_in_2_street_game() {
-        let deal = Deal::new(
-            PlayerCards::new(HoleCards::new_with_rank(1), HoleCards::new_with_rank(2)),
-            Deck::new_empty(),
-            (1.0, 1.0),
-            History::new()
-        );
-        let node = Node::new(&Leduc::new(), deal);
-        let next_node = node.next_action_node(&Leduc::new(), Action::Fold, 1.0);
+        let deal = Leduc::new().generate_deals().remove(0);
+        let arena = TreeArena::new(&Leduc::new());
+        let node = root(&Leduc::new(), &arena, &deal);
+        let next_node = node.next_action_node(&arena, 1, 1.0);
+        assert_eq!(next_node.actions[0], Action::Fold);
+        let next_node = next_node.next_action_node(&arena, 0, 1.0);
         assert_eq!(next_node.is_terminal(&Leduc::new()), true);
     }
 }
@@ -0,0 +1,83 @@
+use crate::visitor::Visitor;
+use crate::node::Node;
+use crate::utils::Utils;
+use std::collections::HashMap;
+
+// Plays the strategy of the regrets at the start of the iteration and
+// collects its updates separately, so threads can share the regrets.
+// The updates of all threads are added to the tables after the iteration.
+pub struct ParallelCfrVisitor<'a> {
+    regrets: &'a [f64],
+    strategy_weight: f64,
+    // Keyed by the info state's offset in the buffers
+    pub regret_updates: HashMap<usize, Vec<f64>>,
+    pub strategy_updates: HashMap<usize, Vec<f64>>,
+}
+
+impl<'a> ParallelCfrVisitor<'a> {
+    pub fn new(regrets: &'a [f64], strategy_weight: f64) -> Self {
+        ParallelCfrVisitor {
+            regrets,
+            strategy_weight,
+            regret_updates: HashMap::new(),
+            strategy_updates: HashMap::new(),
+        }
+    }
+
+    fn update_strategy_sum(&mut self, node: &Node, reach_prob: f64) {
+        let strategy_sum = self.strategy_updates.entry(node.offset)
+            .or_insert_with(|| vec![0.0; node.actions.len()]);
+        for (sum, prob) in strategy_sum.iter_mut().zip(node.action_probs.iter()) {
+            *sum += self.strategy_weight * reach_prob * prob;
+        }
+    }
+
+    fn update_regrets(&mut self, node: &Node) {
+        let regrets = self.regret_updates.entry(node.offset)
+            .or_insert_with(|| vec![0.0; node.actions.len()]);
+        for (regret, action_util) in regrets.iter_mut().zip(node.action_utils.iter()) {
+            *regret += node.opponent_reach_prob() * node.importance_weight() * (action_util - node.util);
+        }
+    }
+}
+
+impl<'a> Visitor for ParallelCfrVisitor<'a> {
+    fn visit_action_node(&mut self, node: &Node) {
+        self.update_strategy_sum(node, node.player_reach_prob());
+        self.update_regrets(node);
+    }
+
+    fn visit_traverser_node(&mut self, node: &Node) {
+        self.update_regrets(node);
+    }
+
+    fn visit_sampled_action_node(&mut self, node: &Node) {
+        self.update_strategy_sum(node, node.importance_weight());
+    }
+
+    fn get_action_probs(&self, node: &Node) -> Vec<f64> {
+        let regrets = &self.regrets[node.buffer_range()];
+        Utils::regret_matching(regrets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deal::Deal;
+
+    #[test]
+    fn test_collects_updates_without_changing_regrets() {
+        let deal = Deal::new_kuhn();
+        let node = Node::new_kuhn_root(&deal);
+        let regrets = vec![3.0, 1.0];
+
+        let mut visitor = ParallelCfrVisitor::new(&regrets, 2.0);
+        visitor.visit_action_node(&node);
+        visitor.visit_action_node(&node);
+
+        assert_eq!(visitor.get_action_probs(&node), vec![0.75, 0.25]);
+        assert_eq!(visitor.regret_updates[&node.offset], vec![4.0, -4.0]);
+        assert_eq!(visitor.strategy_updates[&node.offset], vec![2.0, 2.0]);
+    }
+}
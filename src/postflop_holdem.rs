@@ -7,45 +7,140 @@ use crate::history::History;
 use crate::deck::Deck;
 use crate::street::Street;
 use crate::history_node::HistoryNode;
-use crate::player_cards::PlayerCards;
+use crate::player::Player;
+use crate::bet::Bet;
+use crate::size_menu::SizeMenu;
+use crate::range_deals::RangeDeals;
 
-use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 
 #[derive(Clone, Debug)]
 pub struct PostflopHoldem {
     config: PostflopHoldemConfig,
+    deals: RangeDeals,
 }
 
 impl PostflopHoldem {
     pub fn new(config: PostflopHoldemConfig) -> Self {
         PostflopHoldem {
+            deals: RangeDeals::new(&config.player_range, &config.flop),
             config,
         }
     }
 
-    fn sizes_for_street(&self, street: Street) -> Vec<Action> {
+    fn sizes_for_street(&self, street: &Street) -> &Vec<Bet> {
         match street {
-            Street::Flop(_) => self.config.flop_sizes.iter().map(|size| Action::Bet(size.clone())).collect(),
-            Street::Turn(_) => self.config.turn_sizes.iter().map(|size| Action::Bet(size.clone())).collect(),
-            Street::River(_) => self.config.river_sizes.iter().map(|size| Action::Bet(size.clone())).collect(),
+            Street::Flop(_) => &self.config.flop_sizes,
+            Street::Turn(_) => &self.config.turn_sizes,
+            Street::River(_) => &self.config.river_sizes,
             _ => panic!("Invalid street {}", street.to_string()),
         }
     }
 
-    fn bets_to_raises(&self, bets: Vec<Action>) -> Vec<Action> {
-        bets.into_iter().map(|bet| {
-            match bet {
-                Action::Bet(size) => Action::Raise(size),
-                _ => panic!("Invalid action"),
+    fn raise_sizes_for_street(&self, street: &Street) -> &Vec<Bet> {
+        match street {
+            Street::Flop(_) => &self.config.flop_raise_sizes,
+            Street::Turn(_) => &self.config.turn_raise_sizes,
+            Street::River(_) => &self.config.river_raise_sizes,
+            _ => panic!("Invalid street {}", street.to_string()),
+        }
+    }
+
+    // Sizes of the first bet of the street, None if the line is disabled
+    fn bet_sizes(&self, history: &History, player: Player) -> Option<&Vec<Bet>> {
+        let street_sizes = self.sizes_for_street(history.street());
+        let menu: &SizeMenu = match (player, Self::previous_street_actions(history)) {
+            (Player::IP, _) => &self.config.ip_bet_sizes,
+            (Player::OOP, None) => return Some(street_sizes),
+            (Player::OOP, Some(actions)) => match Self::aggressor(&actions) {
+                Some(Player::IP) => &self.config.donk_sizes,
+                Some(Player::OOP) => return Some(street_sizes),
+                None => &self.config.probe_sizes,
+            },
+        };
+
+        menu.sizes(street_sizes)
+    }
+
+    // Actions of the street before the current one, None on the flop
+    fn previous_street_actions(history: &History) -> Option<Vec<Action>> {
+        let nodes = history.to_vec();
+        let streets: Vec<usize> = nodes.iter().enumerate()
+            .filter(|(_, node)| node.is_street())
+            .map(|(i, _)| i)
+            .collect();
+        if streets.len() < 2 {
+            return None;
+        }
+
+        let (start, end) = (streets[streets.len() - 2], streets[streets.len() - 1]);
+        Some(nodes[start + 1..end].iter().map(|node| node.action()).collect())
+    }
+
+    // Player who bet or raised last in the street's actions, OOP acts first
+    fn aggressor(actions: &[Action]) -> Option<Player> {
+        let i = actions.iter().rposition(|action| matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn))?;
+        Some(if i % 2 == 0 { Player::OOP } else { Player::IP })
+    }
+
+    // Bets, raises and all-ins on the current street after the first one
+    fn street_raises(&self, history: &History) -> usize {
+        let aggressive = history.to_vec().iter().rev()
+            .take_while(|node| node.is_action())
+            .filter(|node| matches!(node.action(), Action::Bet(_) | Action::Raise(_) | Action::AllIn))
+            .count();
+
+        aggressive.saturating_sub(1)
+    }
+
+    // Pot and player to act after the history. OOP acts first on every street.
+    fn replay(&self, history: &History) -> (Pot, Player) {
+        let mut pot = self.initial_pot();
+        let mut player = Player::OOP;
+        // The history starts with the starting street, which the initial pot is on
+        for node in history.to_vec().into_iter().skip(1) {
+            match node {
+                HistoryNode::Action(action) => {
+                    pot.update(player, action);
+                    player = player.opponent();
+                }
+                HistoryNode::Street(_) => {
+                    pot.next_street();
+                    player = Player::OOP;
+                }
             }
-        }).collect()
+        }
+
+        (pot, player)
+    }
+
+    // Sizes that would put in the rest of the stack are replaced by an all-in
+    fn bets(&self, history: &History, raise: bool) -> Vec<Action> {
+        let (pot, player) = self.replay(history);
+        let mut actions: Vec<Action> = if raise {
+            self.raise_sizes_for_street(history.street()).iter().map(|size| Action::Raise(size.clone())).collect()
+        } else {
+            match self.bet_sizes(history, player) {
+                Some(sizes) => sizes.iter().map(|size| Action::Bet(size.clone())).collect(),
+                None => return vec![],
+            }
+        };
+
+        let fits = |action: &Action| pot.added(player, action) < pot.remaining(player);
+        if !actions.iter().all(fits) {
+            actions.retain(fits);
+            actions.push(Action::AllIn);
+        }
+
+        actions
     }
 }
 
 impl Game for PostflopHoldem {
     fn initial_pot(&self) -> Pot {
         let part = self.config.initial_pot/2.0;
-        Pot::new(part, part)
+        let threshold = self.config.all_in_threshold as f64 / 100.0;
+        Pot::new_with_streets(part, part, part + self.config.effective_stack, threshold, self.num_streets() as u32)
     }
 
     fn deck(&self) -> Deck {
@@ -56,28 +151,32 @@ impl Game for PostflopHoldem {
         3
     }
 
+    fn starting_street(&self) -> Street {
+        Street::Flop(self.config.flop.clone())
+    }
+
+    fn signature(&self) -> String {
+        format!("PostflopHoldem {}", self.config.signature())
+    }
+
     fn legal_actions(&self, history: &History) -> Vec<Action> {
-        // TODO: Handle effective stack size
         let last = history.last().unwrap_or(&HistoryNode::Action(Action::Check)).action();
         let mut actions = vec![];
         match last {
-            Action::Check => {
+            Action::Check | Action::None => {
                 actions.push(Action::Check);
-                actions.extend(self.sizes_for_street(history.street().clone()));
+                actions.extend(self.bets(history, false));
             }
-            Action::Bet(_) => {
+            Action::Bet(_) | Action::Raise(_) => {
                 actions.push(Action::Fold);
                 actions.push(Action::Call);
-                actions.extend(self.bets_to_raises(self.sizes_for_street(history.street().clone())));
+                if self.street_raises(history) < self.config.max_raises {
+                    actions.extend(self.bets(history, true));
+                }
             }
-            Action::Raise(_) => {
+            Action::AllIn => {
                 actions.push(Action::Fold);
                 actions.push(Action::Call);
-                actions.extend(self.bets_to_raises(self.sizes_for_street(history.street().clone())));
-            }
-            Action::None => {
-                actions.push(Action::Check);
-                actions.extend(self.sizes_for_street(history.street().clone()));
             }
             _ => ()
         };
@@ -86,42 +185,216 @@ impl Game for PostflopHoldem {
     }
 
     fn legal_first_actions(&self) -> Vec<Action> {
-        let mut actions = vec![Action::Check];
-        actions.extend(self.config.flop_sizes.iter().map(|size| Action::Bet(size.clone())));
+        let mut history = History::new();
+        history.push_street(self.starting_street());
 
-        actions
+        self.legal_actions(&history)
     }
 
     fn generate_deals(&self) -> Vec<Deal> {
-        let mut deals = Vec::new();
-        let oop_range = self.config.oop_range().expand_meta_suits();
-        let ip_range = self.config.ip_range().expand_meta_suits();
-
-        for oop_card in &oop_range {
-            for ip_card in &ip_range {
-                let mut deck = self.deck();
-                deck.remove(&oop_card.0.card1);
-                deck.remove(&oop_card.0.card2);
-                deck.remove(&ip_card.0.card1);
-                deck.remove(&ip_card.0.card2);
-
-                let mut history = History::new();
-                history.push_street(Street::Flop(self.config.flop.clone()));
-                deals.push(
-                    Deal::new(
-                        PlayerCards::new(ip_card.0.clone(), oop_card.0.clone()),
-                        deck,
-                        (*ip_card.1, *oop_card.1),
-                        history,
-                    ),
-                );
-            }
+        self.deals.generate_deals(self)
+    }
+
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal {
+        self.deals.deal(self, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::card::Card;
+    use crate::suit::Suit;
+
+    #[test]
+    fn test_sizes_over_stack_become_all_in() {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.flop_sizes = vec![Bet::P(50), Bet::P(200)];
+        let game = PostflopHoldem::new(config);
+
+        // Pot 53, stack 74
+        assert_eq!(game.legal_first_actions(), vec![Action::Check, Action::Bet(Bet::P(50)), Action::AllIn]);
+    }
+
+    #[test]
+    fn test_raises_use_raise_sizes() {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.flop_raise_sizes = vec![Bet::P(50), Bet::P(75)];
+        let game = PostflopHoldem::new(config.clone());
+        let mut history = History::new();
+        history.push_street(Street::Flop(config.flop));
+        history.push_action(Action::Bet(Bet::P(25)));
+
+        assert_eq!(game.legal_actions(&history),
+            vec![Action::Fold, Action::Call, Action::Raise(Bet::P(50)), Action::Raise(Bet::P(75))]);
+    }
+
+    #[test]
+    fn test_raises_are_capped_per_street() {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.effective_stack = 10000.0;
+        config.max_raises = 2;
+        let game = PostflopHoldem::new(config.clone());
+        let mut history = History::new();
+        history.push_street(Street::Flop(config.flop));
+        history.push_action(Action::Bet(Bet::P(25)));
+        history.push_action(Action::Raise(Bet::P(25)));
+        assert!(game.legal_actions(&history).contains(&Action::Raise(Bet::P(25))));
+
+        history.push_action(Action::Raise(Bet::P(25)));
+        assert_eq!(game.legal_actions(&history), vec![Action::Fold, Action::Call]);
+
+        // The cap starts over on the next street
+        history.push_action(Action::Call);
+        history.push_street(Street::Turn(Board::new()));
+        history.push_action(Action::Bet(Bet::P(125)));
+        assert!(game.legal_actions(&history).contains(&Action::Raise(Bet::P(125))));
+    }
+
+    fn turn_history(config: &PostflopHoldemConfig, flop_actions: Vec<Action>) -> History {
+        let mut history = History::new();
+        history.push_street(Street::Flop(config.flop.clone()));
+        for action in flop_actions {
+            history.push_action(action);
         }
+        let mut board = config.flop.clone();
+        board.push(Card::new(5, Suit::Spades));
+        history.push_street(Street::Turn(board));
+
+        history
+    }
 
-        deals
+    fn size_menu_config() -> PostflopHoldemConfig {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.effective_stack = 1000.0;
+        config.donk_sizes = SizeMenu::Sizes(vec![Bet::P(10)]);
+        config.ip_bet_sizes = SizeMenu::Sizes(vec![Bet::P(33)]);
+        config.probe_sizes = SizeMenu::Sizes(vec![Bet::P(75)]);
+
+        config
+    }
+
+    #[test]
+    fn test_game_ends_on_river() {
+        let config = PostflopHoldemConfig::new_default();
+        let game = PostflopHoldem::new(config.clone());
+        let mut history = turn_history(&config, vec![Action::Check, Action::Check]);
+        history.push_action(Action::Check);
+        history.push_action(Action::Check);
+        assert_eq!(game.final_street(), Street::River(Board::new()).to_u8());
+        assert!(!game.is_terminal(&history));
+
+        let mut board = history.street().board();
+        board.push(Card::new(6, Suit::Spades));
+        history.push_street(Street::River(board));
+        history.push_action(Action::Check);
+        history.push_action(Action::Check);
+        assert!(game.is_terminal(&history));
     }
 
-    fn deal(&self, _rng: &mut StdRng) -> Deal {
-        Deal::new_default()
+    #[test]
+    fn test_donk_sizes() {
+        let config = size_menu_config();
+        let game = PostflopHoldem::new(config.clone());
+        let history = turn_history(&config, vec![Action::Check, Action::Bet(Bet::P(25)), Action::Call]);
+
+        assert_eq!(game.legal_actions(&history), vec![Action::Check, Action::Bet(Bet::P(10))]);
+    }
+
+    #[test]
+    fn test_probe_sizes() {
+        let config = size_menu_config();
+        let game = PostflopHoldem::new(config.clone());
+        let history = turn_history(&config, vec![Action::Check, Action::Check]);
+
+        assert_eq!(game.legal_actions(&history), vec![Action::Check, Action::Bet(Bet::P(75))]);
+    }
+
+    #[test]
+    fn test_continuation_bets_use_street_sizes() {
+        let config = size_menu_config();
+        let game = PostflopHoldem::new(config.clone());
+        let history = turn_history(&config, vec![Action::Bet(Bet::P(25)), Action::Call]);
+
+        assert_eq!(game.legal_first_actions(), vec![Action::Check, Action::Bet(Bet::P(25))]);
+        assert_eq!(game.legal_actions(&history), vec![Action::Check, Action::Bet(Bet::P(125))]);
+    }
+
+    #[test]
+    fn test_ip_bet_sizes() {
+        let config = size_menu_config();
+        let game = PostflopHoldem::new(config.clone());
+        let mut history = turn_history(&config, vec![Action::Bet(Bet::P(25)), Action::Call]);
+        history.push_action(Action::Check);
+
+        assert_eq!(game.legal_actions(&history), vec![Action::Check, Action::Bet(Bet::P(33))]);
+    }
+
+    #[test]
+    fn test_disabled_menu_only_checks() {
+        let mut config = size_menu_config();
+        config.donk_sizes = SizeMenu::Disabled;
+        let game = PostflopHoldem::new(config.clone());
+        let history = turn_history(&config, vec![Action::Check, Action::Bet(Bet::P(25)), Action::Call]);
+
+        assert_eq!(game.legal_actions(&history), vec![Action::Check]);
+    }
+
+    #[test]
+    fn test_all_in_threshold() {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.flop_sizes = vec![Bet::P(25), Bet::P(50)];
+        // A half pot bet leaves 47.5 behind in a pot of 106 once called
+        config.all_in_threshold = 50;
+        let game = PostflopHoldem::new(config);
+
+        assert_eq!(game.legal_first_actions(), vec![Action::Check, Action::Bet(Bet::P(25)), Action::AllIn]);
+    }
+
+    #[test]
+    fn test_geometric_bet_over_remaining_streets() {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.flop_sizes = vec![Bet::G];
+        config.river_sizes = vec![Bet::G];
+        let game = PostflopHoldem::new(config.clone());
+        assert_eq!(game.legal_first_actions(), vec![Action::Check, Action::Bet(Bet::G)]);
+
+        // On the river the geometric size is the rest of the stack
+        let mut history = History::new();
+        let mut street = Street::Flop(config.flop);
+        for card in [Card::new(5, Suit::Spades), Card::new(9, Suit::Clubs)] {
+            history.push_street(street.clone());
+            history.push_action(Action::Check);
+            history.push_action(Action::Check);
+            street = street.next_street(card);
+        }
+        history.push_street(street);
+
+        assert_eq!(game.legal_actions(&history), vec![Action::Check, Action::AllIn]);
+    }
+
+    #[test]
+    fn test_raise_war_ends_all_in() {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.max_raises = 10;
+        let game = PostflopHoldem::new(config.clone());
+        let mut history = History::new();
+        history.push_street(Street::Flop(config.flop));
+
+        loop {
+            let action = game.legal_actions(&history).last().unwrap().clone();
+            history.push_action(action.clone());
+            if action == Action::AllIn {
+                break;
+            }
+        }
+
+        assert_eq!(game.legal_actions(&history), vec![Action::Fold, Action::Call]);
+        history.push_action(Action::Call);
+        assert!(history.is_all_in());
+
+        let (pot, _) = game.replay(&history);
+        assert_eq!(pot.total(), 53.0 + 2.0 * 74.0);
     }
 }
@@ -6,6 +6,7 @@ use crate::range::Range;
 use crate::suit::Suit;
 use crate::card::Card;
 use crate::hole_cards::HoleCards;
+use crate::size_menu::SizeMenu;
 
 #[derive(Clone, Debug)]
 pub struct PostflopHoldemConfig {
@@ -16,6 +17,19 @@ pub struct PostflopHoldemConfig {
     pub flop_sizes: Vec<Bet>,
     pub turn_sizes: Vec<Bet>,
     pub river_sizes: Vec<Bet>,
+    pub flop_raise_sizes: Vec<Bet>,
+    pub turn_raise_sizes: Vec<Bet>,
+    pub river_raise_sizes: Vec<Bet>,
+    // Raises allowed per street after the first bet. All-ins count as raises.
+    pub max_raises: usize,
+    // Bets leaving less than this percentage of the pot behind go all-in
+    pub all_in_threshold: u32,
+    // OOP leading into the aggressor of the previous street
+    pub donk_sizes: SizeMenu,
+    // IP betting after OOP checked
+    pub ip_bet_sizes: SizeMenu,
+    // OOP leading after IP checked back the previous street
+    pub probe_sizes: SizeMenu,
     pub default_size: Bet,
 }
 
@@ -43,10 +57,46 @@ impl PostflopHoldemConfig {
             flop_sizes: vec![Bet::P(25)],
             turn_sizes: vec![Bet::P(125)],
             river_sizes: vec![Bet::P(200)],
+            flop_raise_sizes: vec![Bet::P(25)],
+            turn_raise_sizes: vec![Bet::P(125)],
+            river_raise_sizes: vec![Bet::P(200)],
+            max_raises: 3,
+            all_in_threshold: 0,
+            donk_sizes: SizeMenu::Default,
+            ip_bet_sizes: SizeMenu::Default,
+            probe_sizes: SizeMenu::Default,
             default_size: Bet::P(100),
         }
     }
 
+    // Canonical description of the config
+    pub fn signature(&self) -> String {
+        let sizes_signature = |sizes: &Vec<Bet>| {
+            sizes.iter().map(|size| format!("{:?}", size)).collect::<Vec<String>>().join(",")
+        };
+
+        format!("oop=[{}] ip=[{}] flop={} pot={} stack={} flop_sizes=[{}] turn_sizes=[{}] river_sizes=[{}] \
+            flop_raise_sizes=[{}] turn_raise_sizes=[{}] river_raise_sizes=[{}] max_raises={} all_in_threshold={} \
+            donk_sizes={:?} ip_bet_sizes={:?} probe_sizes={:?} default_size={:?}",
+            self.oop_range().signature(),
+            self.ip_range().signature(),
+            self.flop.to_string(),
+            self.initial_pot,
+            self.effective_stack,
+            sizes_signature(&self.flop_sizes),
+            sizes_signature(&self.turn_sizes),
+            sizes_signature(&self.river_sizes),
+            sizes_signature(&self.flop_raise_sizes),
+            sizes_signature(&self.turn_raise_sizes),
+            sizes_signature(&self.river_raise_sizes),
+            self.max_raises,
+            self.all_in_threshold,
+            self.donk_sizes,
+            self.ip_bet_sizes,
+            self.probe_sizes,
+            self.default_size)
+    }
+
     pub fn oop_range(&self) -> Range {
         self.player_range.get(&Player::OOP).unwrap().clone()
     }
@@ -6,12 +6,49 @@ use crate::bet::Bet;
 #[derive(Clone, Debug)]
 pub struct Pot {
     pot: HashMap<Player, f64>,
+    // Most chips a player can put in the pot, including what they started with
+    limit: f64,
+    // Bets and raises that would leave less than this fraction of the pot
+    // behind go all-in
+    all_in_threshold: f64,
+    // Contribution of both players when the current street started
+    street_start: f64,
+    // Streets left to play, including the current one
+    streets: u32,
 }
 
 impl Pot {
     pub fn new(ip: f64, oop: f64) -> Self {
+        Self::new_with_limit(ip, oop, f64::INFINITY)
+    }
+
+    pub fn new_with_limit(ip: f64, oop: f64, limit: f64) -> Self {
+        Self::new_with_all_in_threshold(ip, oop, limit, 0.0)
+    }
+
+    pub fn new_with_all_in_threshold(ip: f64, oop: f64, limit: f64, all_in_threshold: f64) -> Self {
+        Self::new_with_streets(ip, oop, limit, all_in_threshold, 1)
+    }
+
+    pub fn new_with_streets(ip: f64, oop: f64, limit: f64, all_in_threshold: f64, streets: u32) -> Self {
         Pot {
             pot: HashMap::from([(Player::IP, ip), (Player::OOP, oop)]),
+            limit,
+            all_in_threshold,
+            street_start: ip.min(oop),
+            streets,
+        }
+    }
+
+    // Preflop pot of heads up play, where the small blind is IP. The blinds
+    // are the first bet of the street, antes are dead money.
+    pub fn new_with_blinds(small_blind: f64, big_blind: f64, ante: f64, limit: f64) -> Self {
+        Pot {
+            pot: HashMap::from([(Player::IP, small_blind + ante), (Player::OOP, big_blind + ante)]),
+            limit,
+            all_in_threshold: 0.0,
+            street_start: ante,
+            streets: 1,
         }
     }
 
@@ -19,33 +56,33 @@ impl Pot {
         self.pot[&Player::IP] + self.pot[&Player::OOP]
     }
 
+    // Bets and raises are capped at the player's remaining stack
     pub fn update(&mut self, player: Player, action: Action) {
-        match action {
-            Action::Check | Action::Fold | Action::None => (),
-            Action::Bet(bet) => {
-                let amount = match bet {
-                    Bet::P(p) => self.bet_amount(self.total(), p),
-                    Bet::C(c) => c as f64,
-                };
-                *self.pot.get_mut(&player).unwrap() += amount;
+        let amount = self.added(player, &action).min(self.remaining(player));
+        *self.pot.get_mut(&player).unwrap() += amount;
+    }
 
-            },
-            Action::Raise(amount) => {
-                let to_call = self.to_call();
-                let amount = match amount {
-                    Bet::P(p) => self.bet_amount(self.total() +  to_call, p),
-                    Bet::C(c) => c as f64,
-                };
-
-                *self.pot.get_mut(&player).unwrap() += amount + to_call;
-            },
-            Action::Call => {
-                self.pot.insert(player, self.pot[&player.opponent()]);
+    // Both players have put in the same amount when a street is dealt
+    pub fn next_street(&mut self) {
+        self.street_start = self.pot[&Player::IP];
+        self.streets = self.streets.saturating_sub(1).max(1);
+    }
 
-            },
+    // Chips the action adds to the player's contribution, before capping
+    pub fn added(&self, player: Player, action: &Action) -> f64 {
+        match action {
+            Action::Check | Action::Fold | Action::None => 0.0,
+            Action::Bet(bet) => self.with_all_in_threshold(player, self.bet_size(player, bet)),
+            Action::Raise(bet) => self.with_all_in_threshold(player, self.bet_size(player, bet) + self.to_call()),
+            Action::Call => self.pot[&player.opponent()] - self.pot[&player],
+            Action::AllIn => self.remaining(player),
         }
     }
 
+    pub fn remaining(&self, player: Player) -> f64 {
+        self.limit - self.pot[&player]
+    }
+
     pub fn payoff(&self, player: Player, won: Option<bool>) -> f64 {
         match won {
             Some(true) => {
@@ -57,9 +94,9 @@ impl Pot {
                 -self.pot[&player]
             },
             None => {
-                // In case of draw, pot size should be the same
+                // In case of draw, both get their contribution back
                 assert!(self.pot[&Player::IP] == self.pot[&Player::OOP]);
-                self.pot[&Player::IP]
+                0.0
             },
         }
     }
@@ -76,6 +113,39 @@ impl Pot {
         let bet_fraction = (bet_size as f64) / 100.0;
         pot * bet_fraction
     }
+
+    // Chips the bet adds on top of calling
+    fn bet_size(&self, player: Player, bet: &Bet) -> f64 {
+        let to_call = self.to_call();
+        let pot = self.total() + to_call;
+        match bet {
+            Bet::P(p) => self.bet_amount(pot, *p),
+            Bet::C(c) => *c as f64,
+            Bet::G => {
+                // Bets of the fraction f, each called, grow the pot by 1 + 2f
+                let behind = self.remaining(player) - to_call;
+                let fraction = ((1.0 + 2.0 * behind / pot).powf(1.0 / self.streets as f64) - 1.0) / 2.0;
+                pot * fraction
+            },
+            Bet::X(multiple) => {
+                let previous_bet = self.pot[&player.opponent()] - self.street_start;
+                assert!(previous_bet > 0.0, "Bet::X({}) raises a multiple of the previous bet, but nothing was bet on the street", multiple);
+                let raise_to = self.street_start + previous_bet * (*multiple as f64) / 100.0;
+                (raise_to - self.pot[&player.opponent()]).max(0.0)
+            },
+        }
+    }
+
+    // The rest of the stack if the amount would leave too little behind
+    // once called
+    fn with_all_in_threshold(&self, player: Player, amount: f64) -> f64 {
+        let contribution = self.pot[&player] + amount;
+        if self.limit - contribution < self.all_in_threshold * 2.0 * contribution {
+            self.remaining(player)
+        } else {
+            amount
+        }
+    }
 }
 
 #[cfg(test)]
@@ -106,7 +176,7 @@ mod tests {
     #[test]
     fn test_get_win_amount_on_draw() {
         let pot = Pot::new(1.0, 1.0);
-        assert_eq!(pot.payoff(Player::IP, None), 1.0);
+        assert_eq!(pot.payoff(Player::IP, None), 0.0);
     }
 
     #[test]
@@ -152,6 +222,88 @@ mod tests {
         assert_eq!(pot.pot, HashMap::from([(Player::IP, 6.0), (Player::OOP, 3.0)]));
     }
 
+    #[test]
+    fn test_bet_is_capped_at_stack() {
+        let mut pot = Pot::new_with_limit(1.0, 1.0, 3.0);
+        pot.update(Player::OOP, Action::Bet(Bet::P(200)));
+        assert_eq!(pot.pot[&Player::OOP], 3.0);
+        assert_eq!(pot.remaining(Player::OOP), 0.0);
+    }
+
+    #[test]
+    fn test_update_all_in_call() {
+        let mut pot = Pot::new_with_limit(1.0, 1.0, 10.0);
+        pot.update(Player::OOP, Action::Bet(Bet::P(50)));
+        pot.update(Player::IP, Action::AllIn);
+        pot.update(Player::OOP, Action::Call);
+        assert_eq!(pot.pot, HashMap::from([(Player::IP, 10.0), (Player::OOP, 10.0)]));
+    }
+
+    #[test]
+    fn test_geometric_bet_gets_stacks_in() {
+        let mut pot = Pot::new_with_streets(5.0, 5.0, 50.0, 0.0, 2);
+        for _ in 0..2 {
+            pot.update(Player::OOP, Action::Bet(Bet::G));
+            pot.update(Player::IP, Action::Call);
+            pot.next_street();
+        }
+        assert!((pot.remaining(Player::OOP)).abs() < 1e-9);
+        assert!((pot.remaining(Player::IP)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_raise_multiple_of_previous_bet() {
+        let mut pot = Pot::new(10.0, 10.0);
+        pot.update(Player::OOP, Action::Bet(Bet::C(5)));
+        pot.update(Player::IP, Action::Raise(Bet::X(300)));
+        assert_eq!(pot.pot[&Player::IP], 25.0);
+
+        // Raise to 2.5 times the raise to 15
+        pot.update(Player::OOP, Action::Raise(Bet::X(250)));
+        assert_eq!(pot.pot[&Player::OOP], 47.5);
+    }
+
+    #[test]
+    fn test_raises_over_blinds() {
+        let mut pot = Pot::new_with_blinds(0.5, 1.0, 0.25, 100.0);
+        assert_eq!(pot.total(), 2.0);
+
+        // Open to 2.5 big blinds and 3-bet to 3 times that, antes on top
+        pot.update(Player::IP, Action::Raise(Bet::X(250)));
+        assert_eq!(pot.pot[&Player::IP], 2.75);
+        pot.update(Player::OOP, Action::Raise(Bet::X(300)));
+        assert_eq!(pot.pot[&Player::OOP], 7.75);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing was bet on the street")]
+    fn test_multiple_without_previous_bet() {
+        let mut pot = Pot::new(10.0, 10.0);
+        pot.update(Player::OOP, Action::Bet(Bet::X(300)));
+    }
+
+    #[test]
+    fn test_multiple_follows_streets() {
+        let mut pot = Pot::new(10.0, 10.0);
+        pot.update(Player::OOP, Action::Bet(Bet::C(5)));
+        pot.update(Player::IP, Action::Call);
+        pot.next_street();
+        pot.update(Player::OOP, Action::Bet(Bet::C(10)));
+        pot.update(Player::IP, Action::Raise(Bet::X(300)));
+        assert_eq!(pot.pot[&Player::IP], 45.0);
+    }
+
+    #[test]
+    fn test_all_in_threshold() {
+        // A pot sized bet leaves 40 behind in a pot of 60 once called
+        let mut pot = Pot::new_with_all_in_threshold(10.0, 10.0, 70.0, 0.5);
+        assert_eq!(pot.added(Player::OOP, &Action::Bet(Bet::P(100))), 20.0);
+
+        pot = Pot::new_with_all_in_threshold(10.0, 10.0, 70.0, 0.7);
+        assert_eq!(pot.added(Player::OOP, &Action::Bet(Bet::P(100))), 60.0);
+        assert_eq!(pot.added(Player::OOP, &Action::Check), 0.0);
+    }
+
     #[test]
     fn test_update_bet_raise_call() {
         let mut pot = Pot::new(1.0, 1.0);
@@ -0,0 +1,414 @@
+use crate::game::Game;
+use crate::pot::Pot;
+use crate::preflop_holdem_config::PreflopHoldemConfig;
+use crate::action::Action;
+use crate::deal::Deal;
+use crate::history::History;
+use crate::deck::Deck;
+use crate::history_node::HistoryNode;
+use crate::player::Player;
+use crate::bet::Bet;
+use crate::board::Board;
+use crate::hole_cards::HoleCards;
+use crate::card_set::CardSet;
+use crate::node::Node;
+use crate::equity::Equity;
+use crate::range_deals::RangeDeals;
+use crate::card::Card;
+use crate::suit::Suit;
+
+use rand_chacha::ChaCha12Rng;
+use std::collections::HashMap;
+
+// Heads up preflop betting between the small blind, who is IP, and the big
+// blind. The postflop streets are abstracted away: hands that see a flop
+// split the pot by their equity, see PreflopHoldemConfig. Range vs range
+// walks are not supported, as there is no board to deal.
+#[derive(Clone, Debug)]
+pub struct PreflopHoldem {
+    config: PreflopHoldemConfig,
+    deals: RangeDeals,
+    // All-in equity of the IP hand against the OOP hand, by the canonical
+    // (IP, OOP) pair
+    equities: HashMap<(HoleCards, HoleCards), f64>,
+}
+
+impl PreflopHoldem {
+    pub fn new(config: PreflopHoldemConfig) -> Self {
+        let mut game = PreflopHoldem {
+            deals: RangeDeals::new(&config.player_range, &Board::new()),
+            config,
+            equities: HashMap::new(),
+        };
+        game.equities = game.equities();
+
+        game
+    }
+
+    // Isomorphic pairs of hands share one sample, see canonical
+    fn equities(&self) -> HashMap<(HoleCards, HoleCards), f64> {
+        let equity = Equity::new(self.config.equity_samples, 0);
+        let mut equities = HashMap::new();
+
+        for (ip, _) in self.deals.combos(Player::IP).iter() {
+            for (oop, _) in self.deals.combos(Player::OOP).iter() {
+                if CardSet::from(ip).intersects(&CardSet::from(oop)) {
+                    continue;
+                }
+
+                let (ip, oop) = Self::canonical(ip, oop);
+                if !equities.contains_key(&(ip.clone(), oop.clone())) {
+                    let value = equity.hand_vs_hand(&ip, &oop, &Board::new());
+                    equities.insert((ip, oop), value);
+                }
+            }
+        }
+
+        equities
+    }
+
+    // The hands with their suits renamed in order of appearance. Preflop
+    // equity does not depend on the names of the suits.
+    fn canonical(ip: &HoleCards, oop: &HoleCards) -> (HoleCards, HoleCards) {
+        let mut suits: Vec<Suit> = Vec::with_capacity(4);
+        let mut rename = |card: &Card| {
+            let i = match suits.iter().position(|suit| *suit == card.suit) {
+                Some(i) => i,
+                None => {
+                    suits.push(card.suit);
+                    suits.len() - 1
+                }
+            };
+            Card::new(card.rank, Suit::from_u8(i as u8))
+        };
+
+        let ip = HoleCards::new(&rename(&ip.card1), &rename(&ip.card2));
+        let oop = HoleCards::new(&rename(&oop.card1), &rename(&oop.card2));
+        (ip, oop)
+    }
+
+    // All-in equity of the player to act
+    fn equity(&self, node: &Node) -> f64 {
+        let equity = self.equities[&Self::canonical(&node.cards.get(Player::IP), &node.cards.get(Player::OOP))];
+        if node.player == Player::IP { equity } else { 1.0 - equity }
+    }
+
+    // Equity the player to act realizes. Called all-ins have no postflop
+    // play, other pots use the realization of their pot type.
+    fn realized_equity(&self, node: &Node) -> f64 {
+        let equity = self.equity(node);
+        if node.pot.remaining(node.player) <= 0.0 {
+            return equity;
+        }
+
+        let raises = node.history.to_vec().iter().filter(|node| matches!(node.action(), Action::Raise(_) | Action::AllIn)).count();
+        let realization = match raises {
+            0 => self.config.limp_realization,
+            1 => self.config.raise_realization,
+            _ => self.config.three_bet_realization,
+        };
+        // The factor is the small blind's, relative to the big blind
+        let realization = if node.player == Player::IP { realization } else { 1.0 / realization };
+
+        equity * realization / (equity * realization + 1.0 - equity)
+    }
+
+    // Pot and player to act after the history. The small blind acts first.
+    fn replay(&self, history: &History) -> (Pot, Player) {
+        let mut pot = self.initial_pot();
+        let mut player = self.first_player();
+        for node in history.to_vec() {
+            if let HistoryNode::Action(action) = node {
+                pot.update(player, action);
+                player = player.opponent();
+            }
+        }
+
+        (pot, player)
+    }
+
+    pub fn player_to_act(&self, history: &History) -> Player {
+        self.replay(history).1
+    }
+
+    // Raise sizes by the number of raises so far, the big blind not counting
+    fn raise_sizes(&self, history: &History) -> &[Bet] {
+        let actions: Vec<Action> = history.to_vec().iter().map(|node| node.action()).collect();
+        let raises = actions.iter().filter(|action| matches!(action, Action::Raise(_) | Action::AllIn)).count();
+        match raises {
+            0 if actions.first() == Some(&Action::Call) => &self.config.iso_sizes,
+            0 => &self.config.open_sizes,
+            1 => &self.config.three_bet_sizes,
+            2 => &self.config.four_bet_sizes,
+            _ => &[],
+        }
+    }
+
+    // Sizes that would put in the rest of the stack are replaced by an all-in
+    fn raises(&self, history: &History) -> Vec<Action> {
+        let (pot, player) = self.replay(history);
+        if pot.remaining(player) <= pot.added(player, &Action::Call) {
+            return vec![];
+        }
+
+        let mut actions: Vec<Action> = self.raise_sizes(history).iter().map(|size| Action::Raise(size.clone())).collect();
+        let fits = |action: &Action| pot.added(player, action) < pot.remaining(player);
+        if !actions.iter().all(fits) || self.config.allow_all_in {
+            actions.retain(fits);
+            actions.push(Action::AllIn);
+        }
+
+        actions
+    }
+}
+
+impl Game for PreflopHoldem {
+    fn initial_pot(&self) -> Pot {
+        let config = &self.config;
+        Pot::new_with_blinds(config.small_blind, config.big_blind, config.ante, config.effective_stack)
+    }
+
+    fn deck(&self) -> Deck {
+        Deck::new()
+    }
+
+    fn num_streets(&self) -> u8 {
+        1
+    }
+
+    fn first_player(&self) -> Player {
+        Player::IP
+    }
+
+    fn big_blind(&self) -> f64 {
+        self.config.big_blind
+    }
+
+    fn signature(&self) -> String {
+        format!("PreflopHoldem {}", self.config.signature())
+    }
+
+    fn legal_actions(&self, history: &History) -> Vec<Action> {
+        let mut actions = vec![];
+        match history.last().map(|node| node.action()) {
+            // The small blind facing the big blind
+            None => {
+                actions.push(Action::Fold);
+                if self.config.allow_limp {
+                    actions.push(Action::Call);
+                }
+                actions.extend(self.raises(history));
+            }
+            // The big blind after a limp
+            Some(Action::Call) => {
+                actions.push(Action::Check);
+                actions.extend(self.raises(history));
+            }
+            Some(Action::Raise(_)) => {
+                actions.push(Action::Fold);
+                actions.push(Action::Call);
+                actions.extend(self.raises(history));
+            }
+            Some(Action::AllIn) => {
+                actions.push(Action::Fold);
+                actions.push(Action::Call);
+            }
+            _ => ()
+        };
+
+        actions
+    }
+
+    fn legal_first_actions(&self) -> Vec<Action> {
+        self.legal_actions(&History::new())
+    }
+
+    fn supports_range_vs_range(&self) -> bool {
+        false
+    }
+
+    // Showdowns have no board, see payoff
+    fn player_wins(&self, node: &Node) -> Option<bool> {
+        match node.history.last().unwrap().action() {
+            Action::Fold => Some(true),
+            action => panic!("Preflop showdowns are settled by equity, not by a winner: {:?}", action),
+        }
+    }
+
+    // Hands seeing a flop win their equity share of the pot
+    fn payoff(&self, node: &Node) -> f64 {
+        match node.history.last().unwrap().action() {
+            Action::Fold => node.pot.payoff(node.player, Some(true)),
+            _ => {
+                let equity = self.realized_equity(node);
+                equity * node.pot.payoff(node.player, Some(true)) + (1.0 - equity) * node.pot.payoff(node.player, Some(false))
+            }
+        }
+    }
+
+    fn generate_deals(&self) -> Vec<Deal> {
+        self.deals.generate_deals(self)
+    }
+
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal {
+        self.deals.deal(self, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfr::CFR;
+    use crate::cfr_config::CfrConfig;
+    use crate::range::Range;
+    use crate::tree_arena::TreeArena;
+    use crate::tree_walker::TreeWalker;
+    use crate::training_method::TrainingMethod;
+
+    fn history(actions: Vec<Action>) -> History {
+        History::new_from_vec(actions.into_iter().map(HistoryNode::Action).collect())
+    }
+
+    #[test]
+    fn test_size_menus() {
+        let game = PreflopHoldem::new(PreflopHoldemConfig::new_default());
+
+        assert_eq!(game.legal_first_actions(), vec![Action::Fold, Action::Call, Action::Raise(Bet::X(250)), Action::AllIn]);
+        assert_eq!(game.legal_actions(&history(vec![Action::Call])), vec![Action::Check, Action::Raise(Bet::P(100)), Action::AllIn]);
+
+        let open = vec![Action::Raise(Bet::X(250))];
+        assert_eq!(game.legal_actions(&history(open.clone())), vec![Action::Fold, Action::Call, Action::Raise(Bet::X(300)), Action::AllIn]);
+
+        let four_bet = vec![Action::Raise(Bet::X(250)), Action::Raise(Bet::X(300)), Action::Raise(Bet::X(250))];
+        assert_eq!(game.player_to_act(&history(four_bet.clone())), Player::OOP);
+        assert_eq!(game.legal_actions(&history(four_bet)), vec![Action::Fold, Action::Call, Action::AllIn]);
+    }
+
+    #[test]
+    fn test_blinds_and_antes() {
+        let mut config = PreflopHoldemConfig::new_default();
+        config.ante = 0.5;
+        config.effective_stack = 10.0;
+        config.three_bet_sizes = vec![Bet::X(300), Bet::X(500)];
+        let game = PreflopHoldem::new(config);
+        assert_eq!(game.initial_pot().total(), 2.5);
+
+        // A 3-bet to 12.5 does not fit in the stack of 10
+        let (pot, player) = game.replay(&history(vec![Action::Raise(Bet::X(250))]));
+        assert_eq!(pot.contributions()[&Player::IP], 3.0);
+        assert_eq!(game.raises(&history(vec![Action::Raise(Bet::X(250))])), vec![Action::Raise(Bet::X(300)), Action::AllIn]);
+        assert_eq!(player, Player::OOP);
+    }
+
+    #[test]
+    fn test_preflop_ends_the_game() {
+        let game = PreflopHoldem::new(PreflopHoldemConfig::new_default());
+
+        assert!(!game.is_terminal(&history(vec![Action::Call])));
+        assert!(game.is_terminal(&history(vec![Action::Call, Action::Check])));
+        assert!(game.is_terminal(&history(vec![Action::Raise(Bet::X(250)), Action::Call])));
+        assert!(game.is_terminal(&history(vec![Action::Fold])));
+        assert!(game.is_terminal(&history(vec![Action::AllIn, Action::Call])));
+    }
+
+    #[test]
+    fn test_called_shove_splits_the_pot_by_equity() {
+        let mut config = PreflopHoldemConfig::new_default();
+        config.player_range.insert(Player::IP, Range::new_from_string("AhAd"));
+        config.player_range.insert(Player::OOP, Range::new_from_string("KsKc"));
+        config.effective_stack = 10.0;
+        let game = PreflopHoldem::new(config);
+        let arena = TreeArena::new(&game);
+        let deal = game.generate_deals().remove(0);
+
+        // The small blind shoves and the big blind calls
+        let node = TreeWalker::root_node(&game, &arena, &deal);
+        let shove = node.actions.iter().position(|action| *action == Action::AllIn).unwrap();
+        let node = node.next_action_node(&arena, shove, 1.0);
+        let node = node.next_action_node(&arena, 1, 1.0);
+        assert!(game.is_terminal(node.history));
+
+        // About 82% for aces against kings, the small blind is to act
+        let equity = game.equity(&node);
+        assert!((equity - 0.82).abs() < 0.04, "{}", equity);
+        assert_eq!(game.payoff(&node), equity * 10.0 - (1.0 - equity) * 10.0);
+    }
+
+    #[test]
+    fn test_limped_pot_realizes_equity() {
+        let mut config = PreflopHoldemConfig::new_default();
+        config.player_range.insert(Player::IP, Range::new_from_string("AhAd"));
+        config.player_range.insert(Player::OOP, Range::new_from_string("KsKc"));
+        config.limp_realization = 2.0;
+        let game = PreflopHoldem::new(config);
+        let arena = TreeArena::new(&game);
+        let deal = game.generate_deals().remove(0);
+
+        // The small blind limps and the big blind checks
+        let node = TreeWalker::root_node(&game, &arena, &deal);
+        let node = node.next_action_node(&arena, 1, 1.0);
+        let node = node.next_action_node(&arena, 0, 1.0);
+        assert!(game.is_terminal(node.history));
+        assert_eq!(node.player, Player::IP);
+
+        let equity = game.equity(&node);
+        let realized = equity * 2.0 / (equity * 2.0 + 1.0 - equity);
+        assert_eq!(game.realized_equity(&node), realized);
+        assert_eq!(game.payoff(&node), realized * 1.0 - (1.0 - realized) * 1.0);
+    }
+
+    #[test]
+    fn test_wide_ranges_share_equities() {
+        let mut config = PreflopHoldemConfig::new_default();
+        let range = Range::new_from_string("22+,A2s+,KTs+,QTs+,JTs,A9o+,KTo+");
+        config.player_range.insert(Player::IP, range.clone());
+        config.player_range.insert(Player::OOP, range);
+        config.equity_samples = 20;
+        let game = PreflopHoldem::new(config);
+        let arena = TreeArena::new(&game);
+
+        // One sample per isomorphic pair, and every deal finds its pair
+        let deals = game.generate_deals();
+        assert!(game.equities.len() * 10 < deals.len(), "{} equities for {} deals", game.equities.len(), deals.len());
+        for deal in deals.iter() {
+            let equity = game.equity(&TreeWalker::root_node(&game, &arena, deal));
+            assert!((0.0..=1.0).contains(&equity));
+        }
+    }
+
+    #[test]
+    fn test_canonical_hands() {
+        let canonical = |ip: &str, oop: &str| PreflopHoldem::canonical(&HoleCards::new_from_string(ip), &HoleCards::new_from_string(oop));
+
+        assert_eq!(canonical("AhAd", "KhKc"), canonical("AsAc", "KsKd"));
+        assert_ne!(canonical("AhAd", "KhKc"), canonical("AhAd", "KsKc"));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not support range vs range walks")]
+    fn test_range_vs_range_is_rejected() {
+        let mut config = CfrConfig::new_default();
+        config.method = TrainingMethod::RangeVsRange;
+        CFR::new_with_config(PreflopHoldem::new(PreflopHoldemConfig::new_default()), config);
+    }
+
+    #[test]
+    fn test_push_fold_ranges() {
+        let mut config = PreflopHoldemConfig::new_default();
+        config.player_range.insert(Player::IP, Range::new_from_string("AA,72o"));
+        config.player_range.insert(Player::OOP, Range::new_from_string("AA,32o"));
+        config.effective_stack = 10.0;
+        config.open_sizes = vec![];
+        config.allow_limp = false;
+        let mut cfr = CFR::new_with_config(PreflopHoldem::new(config), CfrConfig::new_default());
+        cfr.train_for_iters(4000);
+
+        // Aces always shove and call, 32o is never ahead of enough shoves to call
+        let shoves = cfr.tree().action_range(Player::IP, &History::new(), &Action::AllIn);
+        assert!(shoves.range[&HoleCards::new_from_string("AhAd")] > 0.95);
+
+        let calls = cfr.tree().action_range(Player::OOP, &history(vec![Action::AllIn]), &Action::Call);
+        assert!(calls.range[&HoleCards::new_from_string("AsAc")] > 0.95);
+        assert!(calls.range[&HoleCards::new_from_string("3h2d")] < 0.05);
+    }
+}
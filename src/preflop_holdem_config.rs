@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use crate::bet::Bet;
+use crate::player::Player;
+use crate::range::Range;
+
+// Hands that see a flop are not played out. A called all-in pays out the
+// raw all-in equity of the hands, other pots the equity each player is
+// assumed to realize postflop, see the realization factors. Building the
+// game samples every pair of hands up to suit isomorphism: 68 pairs for the
+// default ranges, about 4200 for 40 hand classes a side and 97500 for full
+// ranges, which call for fewer equity samples.
+#[derive(Clone, Debug)]
+pub struct PreflopHoldemConfig {
+    // IP is the small blind, OOP the big blind
+    pub player_range: HashMap<Player, Range>,
+    pub small_blind: f64,
+    pub big_blind: f64,
+    pub ante: f64,
+    // Chips each player starts with, blinds and antes included
+    pub effective_stack: f64,
+    // Small blind raising first in
+    pub open_sizes: Vec<Bet>,
+    // Big blind raising a limp
+    pub iso_sizes: Vec<Bet>,
+    pub three_bet_sizes: Vec<Bet>,
+    pub four_bet_sizes: Vec<Bet>,
+    // Small blind calling the big blind
+    pub allow_limp: bool,
+    // All-in at every raise, later raises can only go all-in
+    pub allow_all_in: bool,
+    // Runouts sampled for the equity of each pair of hands seeing a flop
+    pub equity_samples: usize,
+    // Equity realization of the small blind, who is IP postflop, relative
+    // to the big blind in limped, raised and 3-bet or larger pots. Raw
+    // equity e is realized as e * r / (e * r + 1 - e), so 1 keeps it.
+    pub limp_realization: f64,
+    pub raise_realization: f64,
+    pub three_bet_realization: f64,
+}
+
+impl PreflopHoldemConfig {
+    pub fn new_default() -> Self {
+        let range = Range::new_from_string("AA,KK,AKs,QJs,72o");
+        PreflopHoldemConfig {
+            player_range: HashMap::from([(Player::IP, range.clone()), (Player::OOP, range)]),
+            small_blind: 0.5,
+            big_blind: 1.0,
+            ante: 0.0,
+            effective_stack: 100.0,
+            open_sizes: vec![Bet::X(250)],
+            iso_sizes: vec![Bet::P(100)],
+            three_bet_sizes: vec![Bet::X(300)],
+            four_bet_sizes: vec![Bet::X(250)],
+            allow_limp: true,
+            allow_all_in: true,
+            equity_samples: 1000,
+            limp_realization: 1.0,
+            raise_realization: 1.0,
+            three_bet_realization: 1.0,
+        }
+    }
+
+    // Canonical description of the config
+    pub fn signature(&self) -> String {
+        let sizes_signature = |sizes: &Vec<Bet>| {
+            sizes.iter().map(|size| format!("{:?}", size)).collect::<Vec<String>>().join(",")
+        };
+
+        format!("sb=[{}] bb=[{}] blinds={}/{} ante={} stack={} open_sizes=[{}] iso_sizes=[{}] \
+            three_bet_sizes=[{}] four_bet_sizes=[{}] allow_limp={} allow_all_in={} equity_samples={} \
+            realization={}/{}/{}",
+            self.sb_range().signature(),
+            self.bb_range().signature(),
+            self.small_blind,
+            self.big_blind,
+            self.ante,
+            self.effective_stack,
+            sizes_signature(&self.open_sizes),
+            sizes_signature(&self.iso_sizes),
+            sizes_signature(&self.three_bet_sizes),
+            sizes_signature(&self.four_bet_sizes),
+            self.allow_limp,
+            self.allow_all_in,
+            self.equity_samples,
+            self.limp_realization,
+            self.raise_realization,
+            self.three_bet_realization)
+    }
+
+    pub fn sb_range(&self) -> Range {
+        self.player_range.get(&Player::IP).unwrap().clone()
+    }
+
+    pub fn bb_range(&self) -> Range {
+        self.player_range.get(&Player::OOP).unwrap().clone()
+    }
+}
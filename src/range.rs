@@ -1,5 +1,11 @@
+use crate::card_set::CardSet;
 use crate::hole_cards::HoleCards;
+use crate::range_notation;
+use crate::range_parse_error::RangeParseError;
+use crate::range_parser::RangeParser;
+use crate::suit::Suit;
 use std::collections::HashMap;
+use std::str::FromStr;
 
 #[derive(Clone, Debug)]
 pub struct Range {
@@ -50,29 +56,9 @@ impl Range {
         }
     }
 
+    // Panics on malformed input, parse the string for an error instead
     pub fn new_from_string(range_str: &str) -> Self {
-        let mut range = HashMap::new();
-        let parts: Vec<&str> = range_str.split(';').collect();
-
-        for part in parts {
-            let mut split = part.split(':');
-            let hole_cards_str = split.next().unwrap();
-            let weight_str = split.next();
-
-            let hole_cards = HoleCards::new_from_string(hole_cards_str);
-            let weight = match weight_str {
-                Some(w) => w.parse::<f64>().unwrap(),
-                None => 1.0,
-            };
-
-            if weight == 0.0 {
-                continue;
-            }
-
-            range.insert(hole_cards, weight);
-        }
-
-        Range { range }
+        range_str.parse().unwrap_or_else(|err| panic!("Invalid range {}: {}", range_str, err))
     }
 
     pub fn extend(&mut self, other: &Range) {
@@ -81,10 +67,13 @@ impl Range {
         }
     }
 
+    // Exact combos override the class they belong to
     pub fn expand_meta_suits(&self) -> Range {
         let mut expanded_range = HashMap::new();
 
-        for (hole_cards, weight) in &self.range {
+        let mut entries: Vec<(&HoleCards, &f64)> = self.range.iter().collect();
+        entries.sort_by_key(|(hole_cards, _)| hole_cards.expand().len() == 1);
+        for (hole_cards, weight) in entries {
             let expanded_hole_cards = hole_cards.expand();
             for expanded_hole_card in expanded_hole_cards {
                 expanded_range.insert(expanded_hole_card, *weight);
@@ -96,6 +85,99 @@ impl Range {
         }
     }
 
+    // Combo by combo combination of the weights, combos missing from a range
+    // weighing 0. Combos left without weight are dropped.
+    fn combine(&self, other: &Range, f: impl Fn(f64, f64) -> f64) -> Range {
+        let a = self.expand_meta_suits();
+        let b = other.expand_meta_suits();
+        let mut range = HashMap::new();
+        for hole_cards in a.range.keys().chain(b.range.keys()) {
+            let weight = f(a.range.get(hole_cards).copied().unwrap_or(0.0), b.range.get(hole_cards).copied().unwrap_or(0.0));
+            if weight > 0.0 {
+                range.insert(hole_cards.clone(), weight);
+            }
+        }
+
+        Range { range }
+    }
+
+    // Weights may exceed 1, normalize scales them back
+    pub fn add(&self, other: &Range) -> Range {
+        self.combine(other, |a, b| a + b)
+    }
+
+    pub fn multiply(&self, other: &Range) -> Range {
+        self.combine(other, |a, b| a * b)
+    }
+
+    // Weights below 0 are clamped to 0
+    pub fn subtract(&self, other: &Range) -> Range {
+        self.combine(other, |a, b| (a - b).max(0.0))
+    }
+
+    // Scales the weights so that the highest is 1
+    pub fn normalize(&self) -> Range {
+        let max = self.range.values().cloned().fold(0.0, f64::max);
+        if max == 0.0 {
+            return Range { range: HashMap::new() };
+        }
+
+        self.combine(&Range { range: HashMap::new() }, |a, _| a / max)
+    }
+
+    // Combos that share no card with the board or the dead cards
+    pub fn remove_blocked(&self, cards: &CardSet) -> Range {
+        let range = self.expand_meta_suits().range.into_iter()
+            .filter(|(hole_cards, _)| !CardSet::from(hole_cards).intersects(cards))
+            .collect();
+
+        Range { range }
+    }
+
+    // Number of combos, each counted by its weight
+    pub fn combo_count(&self) -> f64 {
+        self.expand_meta_suits().range.values().sum()
+    }
+
+    // One meta hand per pair, suited and offsuit class, weighing the average
+    // of all combos of the class with missing combos as 0
+    pub fn to_classes(&self) -> Range {
+        let expanded = self.expand_meta_suits();
+        let mut totals: HashMap<HoleCards, f64> = HashMap::new();
+        for (hole_cards, weight) in expanded.iter() {
+            *totals.entry(hole_cards.class()).or_insert(0.0) += weight;
+        }
+
+        let range = totals.into_iter()
+            .map(|(class, total)| {
+                let combos = Range::new_pure_range(vec![class.clone()]).expand_meta_suits().range.len();
+                (class, total / combos as f64)
+            })
+            .collect();
+
+        Range { range }
+    }
+
+    // Exchanging suits a and b keeps the weight of every hand
+    pub fn is_suit_symmetric(&self, a: Suit, b: Suit) -> bool {
+        let range = self.expand_meta_suits();
+        range.iter().all(|(hole_cards, weight)| range.range.get(&hole_cards.swap_suits(a, b)) == Some(weight))
+    }
+
+    // Shortest standard notation such as "22+,AJs+,KQo"
+    pub fn to_notation(&self) -> String {
+        range_notation::to_notation(self)
+    }
+
+    // Hands and weights sorted by hole cards, so that equal ranges have
+    // equal signatures whatever their hash order
+    pub fn signature(&self) -> String {
+        let mut combos: Vec<(&HoleCards, &f64)> = self.iter().collect();
+        combos.sort_by(|a, b| a.0.cmp(b.0));
+        combos.iter().map(|(hole_cards, weight)| format!("{}:{}", hole_cards, weight))
+            .collect::<Vec<String>>().join(";")
+    }
+
     pub fn iter(&self) -> RangeIter {
         RangeIter {
             iter: self.range.iter(),
@@ -103,6 +185,15 @@ impl Range {
     }
 }
 
+impl FromStr for Range {
+    type Err = RangeParseError;
+
+    fn from_str(range_str: &str) -> Result<Self, Self::Err> {
+        let hands = RangeParser::new(range_str).parse()?;
+        Ok(Range::new(hands.into_iter().map(|(hole_cards, weight)| (weight, hole_cards)).collect()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::card::Card;
@@ -123,6 +214,24 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_parse_standard_notation() {
+        let range: Range = "22+, ATs+, KTo-K7o, [50]AsKs[/50]".parse().unwrap();
+
+        assert_eq!(range.range.len(), 13 + 4 + 4 + 1);
+        assert_eq!(range.range[&HoleCards::new_from_string("K8o")], 1.0);
+        assert_eq!(range.range[&HoleCards::new_from_string("AsKs")], 0.5);
+        // AsKs is also one of the ATs+ combos
+        assert_eq!(range.expand_meta_suits().range.len(), 13 * 6 + 4 * 4 + 4 * 12);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "AA,AKx".parse::<Range>().unwrap_err();
+        assert_eq!(err.position, 5);
+        assert_eq!(err.to_string(), "Expected ',' or ';' at position 5");
+    }
+
     #[test]
     fn test_extend() {
         let mut range1 = Range::new(vec![(1.0, HoleCards::new(&Card::new(14, Suit::Hearts), &Card::new(14, Suit::Diamonds)))]);
@@ -135,6 +244,78 @@ mod tests {
         assert_eq!(range1.range[&HoleCards::new(&Card::new(13, Suit::Hearts), &Card::new(13, Suit::Diamonds))], 0.5);
     }
 
+    fn weight(range: &Range, hole_cards: &str) -> Option<f64> {
+        range.range.get(&HoleCards::new_from_string(hole_cards)).copied()
+    }
+
+    #[test]
+    fn test_algebra() {
+        let a = Range::new_from_string("AA:0.5,AKs");
+        let b = Range::new_from_string("AA:0.25,AhKh:0.5,KK");
+
+        let sum = a.add(&b);
+        assert_eq!(weight(&sum, "AsAd"), Some(0.75));
+        assert_eq!(weight(&sum, "AhKh"), Some(1.5));
+        assert_eq!(weight(&sum, "KsKd"), Some(1.0));
+
+        let product = a.multiply(&b);
+        assert_eq!(product.range.len(), 6 + 1);
+        assert_eq!(weight(&product, "AsAd"), Some(0.125));
+        assert_eq!(weight(&product, "AhKh"), Some(0.5));
+
+        let difference = a.subtract(&b);
+        assert_eq!(weight(&difference, "AsAd"), Some(0.25));
+        assert_eq!(weight(&difference, "AhKh"), Some(0.5));
+        assert_eq!(weight(&difference, "KsKd"), None);
+
+        let normalized = sum.normalize();
+        assert_eq!(weight(&normalized, "AhKh"), Some(1.0));
+        assert_eq!(weight(&normalized, "AsAd"), Some(0.5));
+    }
+
+    #[test]
+    fn test_remove_blocked_and_combo_count() {
+        let range = Range::new_from_string("AA,AKs:0.5");
+        assert_eq!(range.combo_count(), 6.0 + 2.0);
+
+        let dead = CardSet::from(&HoleCards::new_from_string("AhKd"));
+        let unblocked = range.remove_blocked(&dead);
+        // AhKh and AdKd are blocked
+        assert_eq!(unblocked.combo_count(), 3.0 + 1.0);
+        assert_eq!(weight(&unblocked, "AhKh"), None);
+    }
+
+    #[test]
+    fn test_to_classes() {
+        let classes = Range::new_from_string("AhKh,AsKs:0.5,QQ,72o:0.25").to_classes();
+
+        assert_eq!(classes.range.len(), 3);
+        assert_eq!(weight(&classes, "AKs"), Some(1.5 / 4.0));
+        assert_eq!(weight(&classes, "QQ"), Some(1.0));
+        assert_eq!(weight(&classes, "72o"), Some(0.25));
+        assert_eq!(classes.expand_meta_suits().range.len(), 4 + 6 + 12);
+    }
+
+    #[test]
+    fn test_is_suit_symmetric() {
+        let range = Range::new_from_string("QQ;AKs;AhKd");
+
+        assert!(range.is_suit_symmetric(Suit::Spades, Suit::Clubs));
+        assert!(!range.is_suit_symmetric(Suit::Hearts, Suit::Clubs));
+        assert!(!range.is_suit_symmetric(Suit::Hearts, Suit::Diamonds));
+    }
+
+    #[test]
+    fn test_exact_combos_override_class() {
+        // Every range has its own hash order
+        for _ in 0..20 {
+            let range = Range::new_from_string("AA,AhAd:0.5").expand_meta_suits();
+            assert_eq!(range.range[&HoleCards::new_from_string("AhAd")], 0.5);
+            assert_eq!(range.range[&HoleCards::new_from_string("AsAc")], 1.0);
+            assert_eq!(range.range.len(), 6);
+        }
+    }
+
     #[test]
     fn test_expand_meta_suits() {
         let range_str = "AhAd:1.0;KhKd:0.5;QQ;AQo:0.2;AKs;72o:0";
@@ -178,4 +359,13 @@ mod tests {
             assert_eq!(expanded_range.range[&hole_cards], weight);
         }
     }
+
+    #[test]
+    fn test_signature_sorts_hands() {
+        let range = Range::new_from_string("KK,AA");
+
+        assert_eq!(range.signature(), Range::new_from_string("AA,KK").signature());
+        assert_ne!(range.signature(), Range::new_from_string("AA").signature());
+        assert_eq!(range.signature(), "KoKo:1;AoAo:1");
+    }
 }
@@ -0,0 +1,140 @@
+use crate::game::Game;
+use crate::deal::Deal;
+use crate::deck::Deck;
+use crate::history::History;
+use crate::player_cards::PlayerCards;
+use crate::player::Player;
+use crate::range::Range;
+use crate::board::Board;
+use crate::hole_cards::HoleCards;
+use crate::card_set::CardSet;
+use crate::street::Street;
+
+use rand_chacha::ChaCha12Rng;
+use rand::Rng;
+use std::collections::HashMap;
+
+// Deals of a hand from each player's range on a fixed board, shared by the
+// hold'em games. Weights of the hands are the reach of the deal.
+#[derive(Clone, Debug)]
+pub struct RangeDeals {
+    // Hands of each range by Player::as_usize, sorted so that deals do not
+    // depend on hash order
+    combos: [Vec<(HoleCards, f64)>; 2],
+    board: CardSet,
+}
+
+impl RangeDeals {
+    pub fn new(player_range: &HashMap<Player, Range>, board: &Board) -> Self {
+        let combos = |player: Player| {
+            let range = player_range[&player].expand_meta_suits();
+            let mut combos: Vec<(HoleCards, f64)> = range.iter()
+                .map(|(hole_cards, weight)| (hole_cards.clone(), *weight))
+                .collect();
+            combos.sort_by(|a, b| a.0.cmp(&b.0));
+            combos
+        };
+
+        RangeDeals {
+            combos: [combos(Player::IP), combos(Player::OOP)],
+            board: CardSet::from(board),
+        }
+    }
+
+    pub fn combos(&self, player: Player) -> &[(HoleCards, f64)] {
+        &self.combos[player.as_usize()]
+    }
+
+    // Every pair of hands that can be dealt together
+    pub fn generate_deals<G: Game>(&self, game: &G) -> Vec<Deal> {
+        let mut deals = Vec::new();
+        for oop in self.combos(Player::OOP).iter() {
+            for ip in self.combos(Player::IP).iter() {
+                deals.extend(self.new_deal(game, oop, ip, game.deck()));
+            }
+        }
+
+        deals
+    }
+
+    // Samples a pair of hands uniformly, weights are applied as reach
+    pub fn deal<G: Game>(&self, game: &G, rng: &mut ChaCha12Rng) -> Deal {
+        let (oop_combos, ip_combos) = (self.combos(Player::OOP), self.combos(Player::IP));
+
+        loop {
+            let oop = &oop_combos[rng.gen_range(0..oop_combos.len())];
+            let ip = &ip_combos[rng.gen_range(0..ip_combos.len())];
+            if let Some(deal) = self.new_deal(game, oop, ip, game.shuffled_cards(rng)) {
+                return deal;
+            }
+        }
+    }
+
+    // Hands sharing a card with the board or each other cannot be dealt.
+    // Games starting after preflop begin the history with their street.
+    fn new_deal<G: Game>(&self, game: &G, oop: &(HoleCards, f64), ip: &(HoleCards, f64), mut deck: Deck) -> Option<Deal> {
+        let (oop_cards, ip_cards) = (CardSet::from(&oop.0), CardSet::from(&ip.0));
+        if oop_cards.intersects(&ip_cards) || oop_cards.union(&ip_cards).intersects(&self.board) {
+            return None;
+        }
+
+        deck.remove_all(&self.board.union(&oop_cards).union(&ip_cards));
+
+        let mut history = History::new();
+        let street = game.starting_street();
+        if street != Street::Preflop {
+            history.push_street(street);
+        }
+        Some(Deal::new(
+            PlayerCards::new(ip.0.clone(), oop.0.clone()),
+            deck,
+            (ip.1, oop.1),
+            history,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limit_holdem::LimitHoldem;
+    use crate::limit_holdem_config::LimitHoldemConfig;
+    use rand::SeedableRng;
+
+    fn range_deals(config: &LimitHoldemConfig) -> RangeDeals {
+        RangeDeals::new(&config.player_range, &config.board)
+    }
+
+    #[test]
+    fn test_blocked_hands_are_not_dealt() {
+        let mut config = LimitHoldemConfig::new_default();
+        config.player_range.insert(Player::IP, Range::new_from_string("AhAs;QdQc"));
+        config.player_range.insert(Player::OOP, Range::new_from_string("AhKh;KsKc"));
+        let game = LimitHoldem::new(config.clone());
+        let deals = range_deals(&config).generate_deals(&game);
+
+        // AhAs shares a card with AhKh
+        let dealt: Vec<(HoleCards, HoleCards)> = deals.iter()
+            .map(|deal| (deal.cards.get(Player::IP), deal.cards.get(Player::OOP)))
+            .collect();
+        assert_eq!(dealt.len(), 3);
+        assert!(!dealt.contains(&(HoleCards::new_from_string("AhAs"), HoleCards::new_from_string("AhKh"))));
+        assert!(deals.iter().all(|deal| deal.deck.len() == 52 - 4 - 4));
+        assert!(deals.iter().all(|deal| *deal.history.street() == game.starting_street()));
+    }
+
+    #[test]
+    fn test_sampled_deals_are_dealable() {
+        let config = LimitHoldemConfig::new_default();
+        let game = LimitHoldem::new(config.clone());
+        let deals = range_deals(&config);
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let deal = deals.deal(&game, &mut rng);
+            let (ip, oop) = (CardSet::from(&deal.cards.get(Player::IP)), CardSet::from(&deal.cards.get(Player::OOP)));
+            assert!(!ip.intersects(&oop));
+            assert!(!ip.union(&oop).intersects(&CardSet::from(&config.board)));
+        }
+    }
+}
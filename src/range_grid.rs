@@ -0,0 +1,197 @@
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
+use crate::range::Range;
+use crate::suit::Suit;
+use std::collections::HashMap;
+
+const SIZE: usize = 13;
+
+// 13x13 hand matrix with aces in the first row and column. Pairs are on the
+// diagonal, suited hands above it and offsuit hands below it. Each cell holds
+// a value per hand class, None for classes without combos.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeGrid {
+    pub cells: Vec<Vec<Option<f64>>>,
+}
+
+impl RangeGrid {
+    // Average of the values of the combos present in each class, such as
+    // solver frequencies or EVs. Meta suited and offsuit hands count as
+    // every combo of their class.
+    pub fn new(values: &HashMap<HoleCards, f64>) -> Self {
+        let mut totals = vec![vec![(0.0, 0.0); SIZE]; SIZE];
+        for (hole_cards, value) in values {
+            let (row, col) = Self::cell(hole_cards);
+            let combos = Self::combos(hole_cards);
+            totals[row][col].0 += value * combos;
+            totals[row][col].1 += combos;
+        }
+
+        Self::from_totals(totals, |_, _, count| count)
+    }
+
+    // Weight of each class, combos missing from the range counting as 0
+    pub fn new_from_range(range: &Range) -> Self {
+        let mut totals = vec![vec![(0.0, 0.0); SIZE]; SIZE];
+        for (hole_cards, weight) in range.expand_meta_suits().iter() {
+            let (row, col) = Self::cell(hole_cards);
+            totals[row][col].0 += weight;
+            totals[row][col].1 += 1.0;
+        }
+
+        Self::from_totals(totals, |row, col, _| Self::class_size(row, col))
+    }
+
+    fn from_totals(totals: Vec<Vec<(f64, f64)>>, divisor: impl Fn(usize, usize, f64) -> f64) -> Self {
+        let cells = totals.iter().enumerate()
+            .map(|(row, cells)| cells.iter().enumerate()
+                .map(|(col, (total, count))| if *count > 0.0 { Some(total / divisor(row, col, *count)) } else { None })
+                .collect())
+            .collect();
+
+        RangeGrid {
+            cells,
+        }
+    }
+
+    fn rank(index: usize) -> u8 {
+        14 - index as u8
+    }
+
+    fn index(rank: u8) -> usize {
+        assert!(rank >= 2, "No grid cell for rank {}", rank);
+        (14 - rank) as usize
+    }
+
+    fn cell(hole_cards: &HoleCards) -> (usize, usize) {
+        let high = Self::index(hole_cards.card1.rank);
+        let low = Self::index(hole_cards.card2.rank);
+        if hole_cards.card1.suit == hole_cards.card2.suit && hole_cards.card1.suit != Suit::Offsuit {
+            (high, low)
+        } else {
+            (low, high)
+        }
+    }
+
+    fn class_size(row: usize, col: usize) -> f64 {
+        match row.cmp(&col) {
+            std::cmp::Ordering::Equal => 6.0,
+            std::cmp::Ordering::Less => 4.0,
+            std::cmp::Ordering::Greater => 12.0,
+        }
+    }
+
+    // Combos of a meta hand
+    fn combos(hole_cards: &HoleCards) -> f64 {
+        match hole_cards.card1.suit {
+            Suit::Suited | Suit::Offsuit => {
+                let (row, col) = Self::cell(hole_cards);
+                Self::class_size(row, col)
+            }
+            _ => 1.0,
+        }
+    }
+
+    // Class of the cell, such as AA, AKs or AKo
+    pub fn label(row: usize, col: usize) -> String {
+        let suffix = match row.cmp(&col) {
+            std::cmp::Ordering::Equal => "",
+            std::cmp::Ordering::Less => "s",
+            std::cmp::Ordering::Greater => "o",
+        };
+        let (high, low) = (Self::rank(row.min(col)), Self::rank(row.max(col)));
+        format!("{}{}{}", Card::rank_to_char(high), Card::rank_to_char(low), suffix)
+    }
+
+    fn value(&self, row: usize, col: usize) -> String {
+        self.cells[row][col].map_or(String::new(), |value| format!("{:.2}", value))
+    }
+
+    pub fn to_ascii(&self) -> String {
+        let width = (0..SIZE).flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+            .map(|(row, col)| self.value(row, col).len())
+            .max()
+            .unwrap_or(0);
+
+        let mut ascii = String::new();
+        for row in 0..SIZE {
+            let cells: Vec<String> = (0..SIZE)
+                .map(|col| format!("{:<3} {:>width$}", Self::label(row, col), self.value(row, col), width = width))
+                .collect();
+            ascii.push_str(cells.join(" | ").trim_end());
+            ascii.push('\n');
+        }
+
+        ascii
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table class=\"range-grid\">\n");
+        for row in 0..SIZE {
+            html.push_str("  <tr>");
+            for col in 0..SIZE {
+                let class = match row.cmp(&col) {
+                    std::cmp::Ordering::Equal => "pair",
+                    std::cmp::Ordering::Less => "suited",
+                    std::cmp::Ordering::Greater => "offsuit",
+                };
+                match self.cells[row][col] {
+                    Some(value) => html.push_str(&format!(
+                        "<td class=\"{}\" data-value=\"{}\">{}<br>{:.2}</td>", class, value, Self::label(row, col), value)),
+                    None => html.push_str(&format!("<td class=\"{}\">{}</td>", class, Self::label(row, col))),
+                }
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_weights_per_class() {
+        let grid = RangeGrid::new_from_range(&Range::new_from_string("AA,AKs:0.5,AsKs:1,72o"));
+
+        assert_eq!(grid.cells[0][0], Some(1.0));
+        assert_eq!(grid.cells[0][1], Some((0.5 * 3.0 + 1.0) / 4.0));
+        assert_eq!(grid.cells[12][7], Some(1.0));
+        assert_eq!(grid.cells[1][0], None);
+        assert_eq!(RangeGrid::label(0, 1), "AKs");
+        assert_eq!(RangeGrid::label(12, 7), "72o");
+    }
+
+    #[test]
+    fn test_values_per_combo() {
+        let values = HashMap::from([
+            (HoleCards::new_from_string("AhKd"), 2.0),
+            (HoleCards::new_from_string("AsKc"), -1.0),
+            (HoleCards::new_from_string("QQ"), 0.25),
+        ]);
+        let grid = RangeGrid::new(&values);
+
+        assert_eq!(grid.cells[1][0], Some(0.5));
+        assert_eq!(grid.cells[2][2], Some(0.25));
+        assert_eq!(grid.cells[0][1], None);
+    }
+
+    #[test]
+    fn test_rendering() {
+        let grid = RangeGrid::new_from_range(&Range::new_from_string("AA,AKo:0.5"));
+        let ascii = grid.to_ascii();
+        let lines: Vec<&str> = ascii.lines().collect();
+
+        assert_eq!(lines.len(), 13);
+        assert!(lines[0].starts_with("AA  1.00 | AKs      | AQs"));
+        assert!(lines[1].starts_with("AKo 0.50 | KK       |"));
+
+        let html = grid.to_html();
+        assert!(html.contains("<td class=\"pair\" data-value=\"1\">AA<br>1.00</td>"));
+        assert!(html.contains("<td class=\"suited\">AKs</td>"));
+        assert_eq!(html.matches("<tr>").count(), 13);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
+use crate::range::Range;
+use crate::suit::Suit;
+
+// Weight shared by every combo of the class, 0 for an absent class and None
+// when the combos have different weights
+fn class_weight(range: &Range, class: &HoleCards) -> Option<f64> {
+    let weights: Vec<f64> = class.expand().iter().map(|hole_cards| range.range.get(hole_cards).copied().unwrap_or(0.0)).collect();
+    if weights.iter().all(|weight| *weight == weights[0]) {
+        Some(weights[0])
+    } else {
+        None
+    }
+}
+
+fn with_weight(notation: String, weight: f64) -> String {
+    if weight == 1.0 {
+        notation
+    } else {
+        format!("{}:{}", notation, weight)
+    }
+}
+
+// Runs of equal weights over ranks from high to low. A run reaching the top
+// rank is written with +, other runs as a span.
+fn runs(weights: &[(u8, Option<f64>)], top: u8, label: impl Fn(u8) -> String) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < weights.len() {
+        let (high, weight) = weights[i];
+        let mut j = i + 1;
+        while j < weights.len() && weights[j].1 == weight {
+            j += 1;
+        }
+
+        if let Some(weight) = weight.filter(|weight| *weight > 0.0) {
+            let low = weights[j - 1].0;
+            let notation = if high == low {
+                label(high)
+            } else if high == top {
+                format!("{}+", label(low))
+            } else {
+                format!("{}-{}", label(high), label(low))
+            };
+            items.push(with_weight(notation, weight));
+        }
+        i = j;
+    }
+
+    items
+}
+
+fn length(items: &[String]) -> usize {
+    items.iter().map(|item| item.len() + 1).sum()
+}
+
+// Hands with the given high card, either as suited and offsuit runs or with
+// the kickers both share written once, whichever is shorter
+fn high_card_items(range: &Range, high: u8) -> Vec<String> {
+    let class = |kicker: u8, suit: Suit| HoleCards::new(&Card::new(high, suit), &Card::new(kicker, suit));
+    let label = |kicker: u8, suffix: &str| format!("{}{}{}", Card::rank_to_char(high), Card::rank_to_char(kicker), suffix);
+
+    let kickers: Vec<u8> = (2..high).rev().collect();
+    let suited: Vec<(u8, Option<f64>)> = kickers.iter().map(|&k| (k, class_weight(range, &class(k, Suit::Suited)))).collect();
+    let offsuit: Vec<(u8, Option<f64>)> = kickers.iter().map(|&k| (k, class_weight(range, &class(k, Suit::Offsuit)))).collect();
+
+    let mut separate = runs(&suited, high - 1, |k| label(k, "s"));
+    separate.extend(runs(&offsuit, high - 1, |k| label(k, "o")));
+
+    let both: Vec<(u8, Option<f64>)> = suited.iter().zip(offsuit.iter())
+        .map(|(s, o)| (s.0, if s.1 == o.1 && s.1.is_some_and(|weight| weight > 0.0) { s.1 } else { None }))
+        .collect();
+    let rest = |weights: &[(u8, Option<f64>)]| -> Vec<(u8, Option<f64>)> {
+        weights.iter().zip(both.iter())
+            .map(|(weight, shared)| if shared.1.is_some() { (weight.0, Some(0.0)) } else { *weight })
+            .collect()
+    };
+    let mut merged = runs(&both, high - 1, |k| label(k, ""));
+    merged.extend(runs(&rest(&suited), high - 1, |k| label(k, "s")));
+    merged.extend(runs(&rest(&offsuit), high - 1, |k| label(k, "o")));
+
+    if length(&merged) < length(&separate) {
+        merged
+    } else {
+        separate
+    }
+}
+
+// Shortest standard notation of a range of a standard deck, such as
+// "22+,AJs+,KQo". Classes whose combos have different weights are written
+// out combo by combo.
+pub fn to_notation(range: &Range) -> String {
+    let range = range.expand_meta_suits();
+
+    let pairs: Vec<(u8, Option<f64>)> = (2..=14).rev().map(|rank| (rank, class_weight(&range, &HoleCards::new_with_rank(rank)))).collect();
+    let mut items = runs(&pairs, 14, |rank| HoleCards::new_with_rank(rank).to_notation());
+    for high in (3..=14).rev() {
+        items.extend(high_card_items(&range, high));
+    }
+
+    let mut combos: Vec<(&HoleCards, &f64)> = range.iter()
+        .filter(|(hole_cards, weight)| **weight > 0.0 && class_weight(&range, &hole_cards.class()).is_none())
+        .collect();
+    combos.sort_by(|a, b| b.0.cmp(a.0));
+    items.extend(combos.into_iter().map(|(hole_cards, weight)| with_weight(hole_cards.to_notation(), *weight)));
+
+    items.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notation(range_str: &str) -> String {
+        to_notation(&Range::new_from_string(range_str))
+    }
+
+    #[test]
+    fn test_shortest_notation() {
+        assert_eq!(notation("AA,KK,QQ,JJ,TT,99,88,77,66,55,44,33,22,AKs,AQs,AJs,KQo"), "22+,AJs+,KQo");
+        assert_eq!(notation("KTo-K7o;A5s-A2s;TT-77"), "TT-77,A5s-A2s,KTo-K7o");
+        assert_eq!(notation("AKs,AKo,AQs,AQo,KQs"), "AQ+,KQs");
+        assert_eq!(notation("[50]AA[/50],AKs:0.25"), "AA:0.5,AKs:0.25");
+        assert_eq!(notation(""), "");
+    }
+
+    #[test]
+    fn test_partial_classes_list_combos() {
+        assert_eq!(notation("AsKs,AhKh:0.5,QQ"), "QQ,AhKh:0.5,AsKs");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let range = Range::new_from_string("[40]55-22[/40],TT+,A9s+,A5s-A2s:0.75,KJo+,T9s,9c8c,QJ:0.5");
+        let parsed = Range::new_from_string(&to_notation(&range));
+
+        let expected = range.expand_meta_suits();
+        let actual = parsed.expand_meta_suits();
+        assert_eq!(actual.range.len(), expected.range.len());
+        for (hole_cards, weight) in expected.iter() {
+            assert_eq!(actual.range[hole_cards], *weight, "{}", hole_cards);
+        }
+    }
+
+    #[test]
+    fn test_summed_range_round_trip() {
+        let range = Range::new_from_string("AA:0.5,AKs").add(&Range::new_from_string("AA,AhKh:0.5,KK"));
+        let parsed = Range::new_from_string(&to_notation(&range));
+
+        let expected = range.expand_meta_suits();
+        let actual = parsed.expand_meta_suits();
+        assert_eq!(expected.range[&HoleCards::new_from_string("AhKh")], 1.5);
+        assert_eq!(actual.range.len(), expected.range.len());
+        for (hole_cards, weight) in expected.iter() {
+            assert_eq!(actual.range[hole_cards], *weight, "{}", hole_cards);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use std::fmt;
+
+// Malformed range string, with the character position where parsing failed
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl RangeParseError {
+    pub fn new(position: usize, message: &str) -> Self {
+        RangeParseError {
+            position,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for RangeParseError {}
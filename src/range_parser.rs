@@ -0,0 +1,296 @@
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
+use crate::range_parse_error::RangeParseError;
+use crate::suit::Suit;
+
+// A single hand of range notation before + and - are applied
+#[derive(Clone, Debug, PartialEq)]
+enum Hand {
+    Pair(u8),
+    // High rank, low rank and Suited or Offsuit, None for both
+    Class(u8, u8, Option<Suit>),
+    Combo(Card, Card),
+}
+
+// Parser for range strings such as "22+,ATs+,KTo-K7o,[50]AsKs,QJo[/50],72o:0.25".
+// Items are separated by commas or semicolons, weights are given after a
+// colon or as a percentage for a block of items. Colon weights may exceed
+// 1, as sums of ranges do.
+pub struct RangeParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl RangeParser {
+    pub fn new(range_str: &str) -> Self {
+        RangeParser {
+            chars: range_str.chars().collect(),
+            position: 0,
+        }
+    }
+
+    pub fn parse(&mut self) -> Result<Vec<(HoleCards, f64)>, RangeParseError> {
+        let mut hands = Vec::new();
+        while self.skip_whitespace().is_some() {
+            if self.peek() == Some('[') {
+                hands.extend(self.block()?);
+            } else {
+                hands.extend(self.item(1.0)?);
+            }
+            self.separator()?;
+        }
+
+        Ok(hands)
+    }
+
+    fn error<T>(&self, position: usize, message: &str) -> Result<T, RangeParseError> {
+        Err(RangeParseError::new(position, message))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) -> Option<char> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+        self.peek()
+    }
+
+    // A weight block may follow or close without a separator
+    fn separator(&mut self) -> Result<(), RangeParseError> {
+        match self.skip_whitespace() {
+            Some(',') | Some(';') => {
+                self.position += 1;
+                Ok(())
+            }
+            None | Some('[') => Ok(()),
+            _ => self.error(self.position, "Expected ',' or ';'"),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, RangeParseError> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.position += 1;
+        }
+
+        let number: String = self.chars[start..self.position].iter().collect();
+        match number.parse::<f64>() {
+            Ok(n) => Ok(n),
+            Err(_) => self.error(start, "Expected a number"),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), RangeParseError> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            self.error(self.position, &format!("Expected '{}'", expected))
+        }
+    }
+
+    // [percent]items[/percent]
+    fn block(&mut self) -> Result<Vec<(HoleCards, f64)>, RangeParseError> {
+        let start = self.position;
+        self.expect('[')?;
+        let percent = self.number()?;
+        if percent > 100.0 {
+            return self.error(start + 1, "Weight must be at most 100%");
+        }
+        self.expect(']')?;
+
+        let mut hands = Vec::new();
+        loop {
+            match self.skip_whitespace() {
+                None => return self.error(start, "Unclosed weight block"),
+                Some('[') if self.chars.get(self.position + 1) == Some(&'/') => break,
+                Some('[') => return self.error(self.position, "Weight blocks cannot be nested"),
+                _ => {
+                    hands.extend(self.item(percent / 100.0)?);
+                    self.separator()?;
+                }
+            }
+        }
+
+        self.position += 2;
+        let close = self.position;
+        if self.number()? != percent {
+            return self.error(close, "Closing weight does not match the opening weight");
+        }
+        self.expect(']')?;
+
+        Ok(hands)
+    }
+
+    // hand[+|-hand][:weight]
+    fn item(&mut self, weight: f64) -> Result<Vec<(HoleCards, f64)>, RangeParseError> {
+        let start = self.position;
+        let hand = self.hand()?;
+        let hands = match self.peek() {
+            Some('+') => {
+                self.position += 1;
+                Self::plus(hand).or_else(|message| self.error(start, message))?
+            }
+            Some('-') => {
+                self.position += 1;
+                let end = self.position;
+                let last = self.hand()?;
+                Self::dash(hand, last).or_else(|message| self.error(end, message))?
+            }
+            _ => Self::single(hand),
+        };
+
+        let weight = if self.peek() == Some(':') {
+            self.position += 1;
+            self.number()?
+        } else {
+            weight
+        };
+
+        if weight == 0.0 {
+            return Ok(vec![]);
+        }
+
+        Ok(hands.into_iter().map(|hole_cards| (hole_cards, weight)).collect())
+    }
+
+    fn rank(&mut self) -> Result<u8, RangeParseError> {
+        match self.peek() {
+            Some(c) if "AKQJT98765432".contains(c) => {
+                self.position += 1;
+                Ok(Card::rank_from_char(c))
+            }
+            _ => self.error(self.position, "Expected a rank"),
+        }
+    }
+
+    fn suit(&self) -> Option<Suit> {
+        match self.peek() {
+            Some(c) if "shdc".contains(c) => Some(Suit::from_char(c)),
+            _ => None,
+        }
+    }
+
+    // AA, AK, AKs, AKo or AsKs
+    fn hand(&mut self) -> Result<Hand, RangeParseError> {
+        let start = self.position;
+        let rank1 = self.rank()?;
+
+        if let Some(suit1) = self.suit() {
+            self.position += 1;
+            let rank2 = self.rank()?;
+            let Some(suit2) = self.suit() else {
+                return self.error(self.position, "Expected a suit");
+            };
+            self.position += 1;
+
+            if rank1 == rank2 && suit1 == suit2 {
+                return self.error(start, "Hand uses the same card twice");
+            }
+            return Ok(Hand::Combo(Card::new(rank1, suit1), Card::new(rank2, suit2)));
+        }
+
+        let rank2 = self.rank()?;
+        let suitedness = match self.peek() {
+            Some('s') => Some(Suit::Suited),
+            Some('o') => Some(Suit::Offsuit),
+            _ => None,
+        };
+        if suitedness.is_some() {
+            self.position += 1;
+        }
+
+        if rank1 == rank2 {
+            if suitedness.is_some() {
+                return self.error(start, "Pairs cannot be suited or offsuit");
+            }
+            return Ok(Hand::Pair(rank1));
+        }
+
+        Ok(Hand::Class(rank1.max(rank2), rank1.min(rank2), suitedness))
+    }
+
+    fn class(high: u8, low: u8, suitedness: Option<Suit>) -> Vec<HoleCards> {
+        let suits = match suitedness {
+            Some(suit) => vec![suit],
+            None => vec![Suit::Suited, Suit::Offsuit],
+        };
+
+        suits.into_iter().map(|suit| HoleCards::new(&Card::new(high, suit), &Card::new(low, suit))).collect()
+    }
+
+    fn single(hand: Hand) -> Vec<HoleCards> {
+        match hand {
+            Hand::Pair(rank) => vec![HoleCards::new_with_rank(rank)],
+            Hand::Class(high, low, suitedness) => Self::class(high, low, suitedness),
+            Hand::Combo(card1, card2) => vec![HoleCards::new(&card1, &card2)],
+        }
+    }
+
+    // Pairs up to aces, or kickers up to one below the high card
+    fn plus(hand: Hand) -> Result<Vec<HoleCards>, &'static str> {
+        match hand {
+            Hand::Pair(rank) => Ok((rank..=14).map(HoleCards::new_with_rank).collect()),
+            Hand::Class(high, low, suitedness) => Ok((low..high).flat_map(|kicker| Self::class(high, kicker, suitedness)).collect()),
+            Hand::Combo(..) => Err("'+' cannot follow exact cards"),
+        }
+    }
+
+    fn dash(first: Hand, last: Hand) -> Result<Vec<HoleCards>, &'static str> {
+        match (first, last) {
+            (Hand::Pair(a), Hand::Pair(b)) => Ok((a.min(b)..=a.max(b)).map(HoleCards::new_with_rank).collect()),
+            (Hand::Class(high1, low1, suitedness1), Hand::Class(high2, low2, suitedness2))
+                if high1 == high2 && suitedness1 == suitedness2 =>
+            {
+                Ok((low1.min(low2)..=low1.max(low2)).flat_map(|kicker| Self::class(high1, kicker, suitedness1)).collect())
+            }
+            _ => Err("Range must span pairs or hands with the same high card and suits"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(range_str: &str) -> Result<Vec<String>, RangeParseError> {
+        let hands = RangeParser::new(range_str).parse()?;
+        Ok(hands.iter().map(|(hole_cards, weight)| format!("{}:{}", hole_cards, weight)).collect())
+    }
+
+    fn class(s: &str, weight: f64) -> String {
+        format!("{}:{}", HoleCards::new_from_string(s), weight)
+    }
+
+    #[test]
+    fn test_plus_and_dash() {
+        assert_eq!(parse("QQ+").unwrap(), vec![class("QQ", 1.0), class("KK", 1.0), class("AA", 1.0)]);
+        assert_eq!(parse("KTo-K8o").unwrap(), vec![class("K8o", 1.0), class("K9o", 1.0), class("KTo", 1.0)]);
+        assert_eq!(parse("A3s-A2s, 33-22").unwrap(), vec![class("A2s", 1.0), class("A3s", 1.0), class("22", 1.0), class("33", 1.0)]);
+        assert_eq!(parse("QJs+").unwrap(), vec![class("QJs", 1.0)]);
+        assert_eq!(parse("KQ").unwrap(), vec![class("KQs", 1.0), class("KQo", 1.0)]);
+    }
+
+    #[test]
+    fn test_weights() {
+        assert_eq!(parse("AsKs:0.5;72o:0").unwrap(), vec![class("AsKs", 0.5)]);
+        assert_eq!(
+            parse("[50]AA, KQs[/50], JJ,[25]T9s[/25]").unwrap(),
+            vec![class("AA", 0.5), class("KQs", 0.5), class("JJ", 1.0), class("T9s", 0.25)]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse("AA,KX").unwrap_err(), RangeParseError::new(4, "Expected a rank"));
+        assert_eq!(parse("AsKs+").unwrap_err(), RangeParseError::new(0, "'+' cannot follow exact cards"));
+        assert_eq!(parse("AKs-QJs").unwrap_err().position, 4);
+        assert_eq!(parse("AAs").unwrap_err().position, 0);
+        assert_eq!(parse("AK:x").unwrap_err(), RangeParseError::new(3, "Expected a number"));
+        assert_eq!(parse("[50]AA").unwrap_err(), RangeParseError::new(0, "Unclosed weight block"));
+        assert_eq!(parse("[50]AA[/40]").unwrap_err().position, 8);
+        assert_eq!(parse("AA KK").unwrap_err(), RangeParseError::new(3, "Expected ',' or ';'"));
+    }
+}
@@ -0,0 +1,445 @@
+use crate::board::Board;
+use crate::card_removal::CardRemoval;
+use crate::cfr_variant::CfrVariant;
+use crate::discounted_cfr_visitor::DiscountedCfrVisitor;
+use crate::game::Game;
+use crate::hole_cards::HoleCards;
+use crate::player::Player;
+use crate::pot::Pot;
+use crate::range::Range;
+use crate::showdown::Showdown;
+use crate::suit::Suit;
+use crate::suit_isomorphism::SuitIsomorphism;
+use crate::tree_arena::TreeArena;
+use crate::utils::Utils;
+use std::collections::HashMap;
+
+// Walks the public tree once per player and iteration with reach
+// probability vectors over all hands of both players, instead of once per
+// pair of hands. Cards held by a hand remove the opponent hands sharing
+// them. Chance deals are uniform over the cards no player holds, so games
+// must have distinct cards, e.g. Kuhn or PostflopHoldem.
+//
+// Chance cards isomorphic to an earlier card of the same chance node are not
+// walked. Their values are the earlier card's with the suits of the hands
+// swapped, and their subtrees copy the earlier card's regrets.
+pub struct RangeWalker {
+    // Weight of each hand in its player's range, by Player::as_usize
+    weights: [Vec<f64>; 2],
+    removal: CardRemoval,
+    // Chips put in by each player at each arena node, by Player::as_usize
+    contributions: Vec<[f64; 2]>,
+    showdowns: Vec<Showdown>,
+    // Showdown of each terminal node that ends in a showdown
+    node_showdowns: Vec<Option<usize>>,
+    // Cards held by the players, which chance cannot deal
+    held_cards: usize,
+    // Weight of all pairs of hands that share no card
+    pair_weight: f64,
+    // Suits exchanged by each hand swap
+    swap_suits: Vec<(Suit, Suit)>,
+    // Index of the swapped hand of each hand, by Player::as_usize
+    hand_swaps: Vec<[Vec<usize>; 2]>,
+    // For each child of each chance node, the earlier child it is
+    // isomorphic to and the hand swap between them
+    isomorphic_cards: Vec<Vec<Option<(usize, usize)>>>,
+    // Canonical node, isomorphic node and hand swap, deepest first
+    mirrors: Vec<(usize, usize, usize)>,
+}
+
+// Regrets and strategy sums updated by the traverser's walk
+struct Updates<'a> {
+    regrets: &'a mut [f64],
+    strategy_sum: &'a mut [f64],
+    traverser: Player,
+    strategy_weight: f64,
+    plus: bool,
+}
+
+impl RangeWalker {
+    pub fn new<G: Game>(game: &G, arena: &TreeArena) -> Self {
+        Self::new_with_isomorphism(game, arena, true)
+    }
+
+    pub fn new_with_isomorphism<G: Game>(game: &G, arena: &TreeArena, isomorphism: bool) -> Self {
+        let hands = [arena.hands(Player::IP).to_vec(), arena.hands(Player::OOP).to_vec()];
+        let removal = CardRemoval::new(&hands);
+        let board = arena.nodes[0].history.street().board();
+
+        let mut weights = [vec![0.0; hands[0].len()], vec![0.0; hands[1].len()]];
+        for deal in game.generate_deals() {
+            for (player, weight) in [(Player::IP, deal.weights.0), (Player::OOP, deal.weights.1)] {
+                let hand = arena.hand_index(player, &deal.cards.get(player)).unwrap();
+                weights[player.as_usize()][hand] = weight;
+            }
+        }
+        for (player_hands, player_weights) in hands.iter().zip(weights.iter_mut()) {
+            for (hand, weight) in player_hands.iter().zip(player_weights.iter_mut()) {
+                if hand.cards().iter().any(|card| board.cards.contains(card)) {
+                    *weight = 0.0;
+                }
+            }
+        }
+
+        let mut walker = RangeWalker {
+            weights,
+            removal,
+            contributions: vec![[0.0, 0.0]; arena.nodes.len()],
+            showdowns: Vec::new(),
+            node_showdowns: vec![None; arena.nodes.len()],
+            held_cards: hands.iter().map(|player_hands| Self::card_count(&player_hands[0])).sum(),
+            pair_weight: 0.0,
+            swap_suits: Vec::new(),
+            hand_swaps: Vec::new(),
+            isomorphic_cards: vec![Vec::new(); arena.nodes.len()],
+            mirrors: Vec::new(),
+        };
+
+        if isomorphism {
+            let ranges = [0, 1].map(|p| Range::new(walker.weights[p].iter().cloned().zip(hands[p].iter().cloned()).collect()));
+            walker.add_isomorphisms(arena, 0, &ranges);
+        }
+        walker.add_contributions(arena, 0, game.initial_pot());
+        walker.add_showdowns(arena, &hands);
+
+        let (total, card_sums) = walker.removal.card_sums(Player::IP, &walker.weights[0]);
+        walker.pair_weight = walker.weights[1].iter().enumerate()
+            .map(|(h, weight)| weight * walker.removal.exclude(Player::OOP, h, total, &card_sums, Some(&walker.weights[0])))
+            .sum();
+
+        walker
+    }
+
+    fn card_count(hand: &HoleCards) -> usize {
+        if hand.card1 == hand.card2 { 1 } else { 2 }
+    }
+
+    fn add_isomorphisms(&mut self, arena: &TreeArena, index: usize, ranges: &[Range; 2]) {
+        let node = &arena.nodes[index];
+        if !node.actions.is_empty() || node.children.is_empty() {
+            for &child in node.children.iter() {
+                self.add_isomorphisms(arena, child, ranges);
+            }
+            return;
+        }
+
+        let boards: Vec<Board> = node.history.to_vec().iter()
+            .filter(|history_node| history_node.is_street())
+            .map(|history_node| history_node.street().board())
+            .collect();
+        let isomorphism = SuitIsomorphism::new(&boards, ranges);
+        let isomorphic: Vec<Option<(usize, usize)>> = (0..node.cards.len())
+            .map(|i| isomorphism.isomorphic_card(&node.cards, i)
+                .map(|(j, s)| (j, self.hand_swap(arena, isomorphism.swaps[s]))))
+            .collect();
+
+        // Mirrors inside the canonical subtrees are copied before the
+        // subtrees themselves
+        for (i, &child) in node.children.iter().enumerate() {
+            if isomorphic[i].is_none() {
+                self.add_isomorphisms(arena, child, ranges);
+            }
+        }
+        for (i, &child) in node.children.iter().enumerate() {
+            if let Some((j, swap)) = isomorphic[i] {
+                self.add_mirrors(arena, node.children[j], child, swap);
+            }
+        }
+
+        self.isomorphic_cards[index] = isomorphic;
+    }
+
+    fn hand_swap(&mut self, arena: &TreeArena, suits: (Suit, Suit)) -> usize {
+        if let Some(i) = self.swap_suits.iter().position(|swap_suits| *swap_suits == suits) {
+            return i;
+        }
+
+        let (a, b) = suits;
+        let swapped = [Player::IP, Player::OOP].map(|player| arena.hands(player).iter()
+            .map(|hand| arena.hand_index(player, &hand.swap_suits(a, b)).unwrap())
+            .collect());
+        self.swap_suits.push(suits);
+        self.hand_swaps.push(swapped);
+        self.hand_swaps.len() - 1
+    }
+
+    fn add_mirrors(&mut self, arena: &TreeArena, canonical: usize, isomorphic: usize, swap: usize) {
+        let (a, b) = self.swap_suits[swap];
+        let (canonical_node, isomorphic_node) = (&arena.nodes[canonical], &arena.nodes[isomorphic]);
+        if isomorphic_node.actions.is_empty() {
+            for (card, &child) in isomorphic_node.cards.iter().zip(isomorphic_node.children.iter()) {
+                self.add_mirrors(arena, canonical_node.chance_child(&card.swap_suits(a, b)), child, swap);
+            }
+        } else {
+            self.mirrors.push((canonical, isomorphic, swap));
+            for (&canonical_child, &child) in canonical_node.children.iter().zip(isomorphic_node.children.iter()) {
+                self.add_mirrors(arena, canonical_child, child, swap);
+            }
+        }
+    }
+
+    fn add_contributions(&mut self, arena: &TreeArena, index: usize, pot: Pot) {
+        let contributions = pot.contributions();
+        self.contributions[index] = [contributions[&Player::IP], contributions[&Player::OOP]];
+
+        let node = &arena.nodes[index];
+        if node.actions.is_empty() {
+            let mut next_pot = pot.clone();
+            next_pot.next_street();
+            for &child in node.children.iter() {
+                self.add_contributions(arena, child, next_pot.clone());
+            }
+        } else {
+            for (action, &child) in node.actions.iter().zip(node.children.iter()) {
+                let mut next_pot = pot.clone();
+                next_pot.update(node.player, action.clone());
+                self.add_contributions(arena, child, next_pot);
+            }
+        }
+    }
+
+    fn add_showdowns(&mut self, arena: &TreeArena, hands: &[Vec<HoleCards>; 2]) {
+        let mut board_showdowns: HashMap<Board, usize> = HashMap::new();
+        for (index, node) in arena.nodes.iter().enumerate() {
+            if !node.children.is_empty() || node.history.is_terminal_action() {
+                continue;
+            }
+
+            let board = node.history.street().board();
+            let showdown = *board_showdowns.entry(board.clone()).or_insert_with(|| {
+                self.showdowns.push(Showdown::new(hands, &board));
+                self.showdowns.len() - 1
+            });
+            self.node_showdowns[index] = Some(showdown);
+        }
+    }
+
+    // Runs one iteration with alternating updates, OOP first. Returns the
+    // EV of OOP under the strategies at the start of the iteration.
+    pub fn iterate(&self, arena: &TreeArena, regrets: &mut [f64], strategy_sum: &mut [f64], variant: CfrVariant, iteration: usize) -> f64 {
+        if let CfrVariant::Discounted { alpha, beta, gamma } = variant {
+            DiscountedCfrVisitor::new(regrets, strategy_sum, iteration, alpha, beta, gamma).discount_all();
+        }
+
+        let mut updates = Updates {
+            regrets,
+            strategy_sum,
+            traverser: Player::OOP,
+            strategy_weight: if variant == CfrVariant::Plus { iteration as f64 } else { 1.0 },
+            plus: variant == CfrVariant::Plus,
+        };
+
+        let values = self.walk(arena, &mut updates, 0, &self.weights);
+        updates.traverser = Player::IP;
+        self.walk(arena, &mut updates, 0, &self.weights);
+        self.mirror(arena, updates.regrets, updates.strategy_sum);
+
+        let oop_weights = &self.weights[Player::OOP.as_usize()];
+        values.iter().zip(oop_weights.iter()).map(|(value, weight)| value * weight).sum::<f64>() / self.pair_weight
+    }
+
+    // Copies the regrets and strategy sums of canonical subtrees to their
+    // isomorphic subtrees
+    fn mirror(&self, arena: &TreeArena, regrets: &mut [f64], strategy_sum: &mut [f64]) {
+        for &(canonical, isomorphic, swap) in self.mirrors.iter() {
+            let (canonical_node, isomorphic_node) = (&arena.nodes[canonical], &arena.nodes[isomorphic]);
+            let actions = isomorphic_node.actions.len();
+            let swapped = &self.hand_swaps[swap][isomorphic_node.player.as_usize()];
+            for (h, &swapped_hand) in swapped.iter().enumerate() {
+                let from = canonical_node.offset + swapped_hand * actions;
+                let to = isomorphic_node.offset + h * actions;
+                regrets.copy_within(from..from + actions, to);
+                strategy_sum.copy_within(from..from + actions, to);
+            }
+        }
+    }
+
+    // Counterfactual values of the traverser's hands, given the reach of
+    // both players' hands
+    fn walk(&self, arena: &TreeArena, updates: &mut Updates, index: usize, reach: &[Vec<f64>; 2]) -> Vec<f64> {
+        let node = &arena.nodes[index];
+        let traverser = updates.traverser;
+        let opponent = traverser.opponent();
+        let (p, o) = (traverser.as_usize(), opponent.as_usize());
+
+        if node.children.is_empty() {
+            let contributions = self.contributions[index];
+            if let Some(showdown) = self.node_showdowns[index] {
+                return self.showdowns[showdown].values(
+                    traverser, &self.removal, &reach[o], contributions[o], contributions[p]);
+            }
+
+            // The player who folded acted last
+            let payoff = if node.player.opponent() == traverser { -contributions[p] } else { contributions[o] };
+            let (total, card_sums) = self.removal.card_sums(opponent, &reach[o]);
+            return (0..reach[p].len())
+                .map(|h| payoff * self.removal.exclude(traverser, h, total, &card_sums, Some(&reach[o])))
+                .collect();
+        }
+
+        let mut values = vec![0.0; reach[p].len()];
+
+        if node.actions.is_empty() {
+            let mut card_values: Vec<Vec<f64>> = Vec::with_capacity(node.cards.len());
+            for (i, (card, &child)) in node.cards.iter().zip(node.children.iter()).enumerate() {
+                let child_values = match self.isomorphic_cards[index].get(i) {
+                    Some(&Some((j, swap))) => self.hand_swaps[swap][p].iter().map(|&h| card_values[j][h]).collect(),
+                    _ => {
+                        let mut next_reach = reach.clone();
+                        for player in [Player::IP, Player::OOP] {
+                            for (h, r) in next_reach[player.as_usize()].iter_mut().enumerate() {
+                                if self.removal.contains(player, h, card) {
+                                    *r = 0.0;
+                                }
+                            }
+                        }
+
+                        self.walk(arena, updates, child, &next_reach)
+                    }
+                };
+
+                for (h, value) in values.iter_mut().enumerate() {
+                    if !self.removal.contains(traverser, h, card) {
+                        *value += child_values[h];
+                    }
+                }
+                card_values.push(child_values);
+            }
+
+            let deals = (node.cards.len() - self.held_cards) as f64;
+            return values.iter().map(|value| value / deals).collect();
+        }
+
+        let actions = node.actions.len();
+        let player = node.player.as_usize();
+        let strategy: Vec<Vec<f64>> = (0..reach[player].len())
+            .map(|h| {
+                let start = node.offset + h * actions;
+                Utils::regret_matching(&updates.regrets[start..start + actions])
+            })
+            .collect();
+
+        let mut action_values = Vec::with_capacity(actions);
+        for (a, &child) in node.children.iter().enumerate() {
+            let mut next_reach = reach.clone();
+            for (r, hand_strategy) in next_reach[player].iter_mut().zip(strategy.iter()) {
+                *r *= hand_strategy[a];
+            }
+
+            let child_values = self.walk(arena, updates, child, &next_reach);
+            for (h, value) in values.iter_mut().enumerate() {
+                *value += if player == p { strategy[h][a] * child_values[h] } else { child_values[h] };
+            }
+            action_values.push(child_values);
+        }
+
+        if player == p {
+            for (h, hand_strategy) in strategy.iter().enumerate() {
+                let start = node.offset + h * actions;
+                for a in 0..actions {
+                    let regret = &mut updates.regrets[start + a];
+                    *regret += action_values[a][h] - values[h];
+                    if updates.plus && *regret < 0.0 {
+                        *regret = 0.0;
+                    }
+
+                    updates.strategy_sum[start + a] += updates.strategy_weight * reach[p][h] * hand_strategy[a];
+                }
+            }
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_tree::GameTree;
+    use crate::info_state::InfoState;
+    use crate::kuhn::Kuhn;
+    use crate::postflop_holdem::PostflopHoldem;
+    use crate::postflop_holdem_config::PostflopHoldemConfig;
+    use crate::range::Range;
+    use crate::statistics_visitor::StatisticsVisitor;
+    use crate::card::Card;
+
+    // EV of the first iteration, where both players play uniformly
+    fn first_iteration_ev<G: Game + Clone>(game: G) -> (f64, f64) {
+        let mut tree = GameTree::new(game);
+        tree.build();
+        let mut statistics = StatisticsVisitor::new(&tree);
+        statistics.build();
+        let uniform_ev = statistics.node_util(&InfoState::new_empty());
+
+        let walker = RangeWalker::new(&tree.game, &tree.arena);
+        let mut regrets = tree.regrets.clone();
+        let mut strategy_sum = tree.strategy_sum.clone();
+        let ev = walker.iterate(&tree.arena, &mut regrets, &mut strategy_sum, CfrVariant::Vanilla, 1);
+
+        (ev, uniform_ev)
+    }
+
+    #[test]
+    fn test_kuhn_uniform_ev() {
+        let (ev, uniform_ev) = first_iteration_ev(Kuhn::new());
+        assert!((ev - uniform_ev).abs() < 1e-12, "{} != {}", ev, uniform_ev);
+    }
+
+    #[test]
+    fn test_postflop_check_down_ev_with_card_removal() {
+        // KK against KK splits the pot on most runouts
+        let mut config = PostflopHoldemConfig::new_default();
+        config.player_range.insert(Player::IP, Range::new_from_string("AA;KK"));
+        config.player_range.insert(Player::OOP, Range::new_from_string("AKs;QQ;KK"));
+        config.flop_sizes = vec![];
+        config.turn_sizes = vec![];
+        config.river_sizes = vec![];
+
+        let (ev, uniform_ev) = first_iteration_ev(PostflopHoldem::new(config));
+        assert!((ev - uniform_ev).abs() < 1e-9, "{} != {}", ev, uniform_ev);
+    }
+
+    // Bets only on the flop, whose other suits are interchangeable
+    fn monotone_config() -> PostflopHoldemConfig {
+        let mut config = PostflopHoldemConfig::new_default();
+        config.player_range.insert(Player::IP, Range::new_from_string("QQ"));
+        config.player_range.insert(Player::OOP, Range::new_from_string("KK;AKs"));
+        config.flop = Board::from_vec(vec![
+            Card::new(14, Suit::Spades),
+            Card::new(11, Suit::Spades),
+            Card::new(2, Suit::Spades),
+        ]);
+        config.flop_raise_sizes = vec![];
+        config.turn_sizes = vec![];
+        config.river_sizes = vec![];
+
+        config
+    }
+
+    #[test]
+    fn test_isomorphism_matches_full_walk() {
+        let mut tree = GameTree::new(PostflopHoldem::new(monotone_config()));
+        tree.build();
+        let train = |isomorphism: bool| {
+            let walker = RangeWalker::new_with_isomorphism(&tree.game, &tree.arena, isomorphism);
+            let mut regrets = tree.regrets.clone();
+            let mut strategy_sum = tree.strategy_sum.clone();
+            let evs: Vec<f64> = (1..=5)
+                .map(|i| walker.iterate(&tree.arena, &mut regrets, &mut strategy_sum, CfrVariant::Plus, i))
+                .collect();
+            (walker.mirrors.len(), evs, strategy_sum)
+        };
+
+        let (mirrors, evs, strategy_sum) = train(true);
+        let (_, full_evs, full_strategy_sum) = train(false);
+
+        assert!(mirrors > 0);
+        for (ev, full_ev) in evs.iter().zip(full_evs.iter()) {
+            assert!((ev - full_ev).abs() < 1e-9, "{} != {}", ev, full_ev);
+        }
+        for (sum, full_sum) in strategy_sum.iter().zip(full_strategy_sum.iter()) {
+            assert!((sum - full_sum).abs() < 1e-6, "{} != {}", sum, full_sum);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::board::Board;
+use crate::card_removal::CardRemoval;
+use crate::hand_evaluator::hand_strength;
+use crate::hole_cards::HoleCards;
+use crate::player::Player;
+
+// Hands of both players ranked on a board. Evaluates a showdown against a
+// whole opponent range with prefix sums over the hands sorted by strength.
+pub struct Showdown {
+    // Strength of each hand, by Player::as_usize. Higher wins.
+    strengths: [Vec<u32>; 2],
+    // Hand indices by increasing strength
+    order: [Vec<usize>; 2],
+}
+
+impl Showdown {
+    pub fn new(hands: &[Vec<HoleCards>; 2], board: &Board) -> Self {
+        // Hands sharing a card with the board cannot be dealt and stay
+        // weakest with strength 0
+        let strengths = [0, 1].map(|p| hands[p].iter()
+            .map(|hand| {
+                if hand.cards().iter().any(|card| board.cards.contains(card)) { 0 } else { hand_strength(hand, board) }
+            })
+            .collect::<Vec<u32>>());
+        let order = [0, 1].map(|p| {
+            let mut order: Vec<usize> = (0..hands[p].len()).collect();
+            order.sort_by_key(|&h| strengths[p][h]);
+            order
+        });
+
+        Showdown {
+            strengths,
+            order,
+        }
+    }
+
+    pub fn strength(&self, player: Player, hand: usize) -> u32 {
+        self.strengths[player.as_usize()][hand]
+    }
+
+    // Value of each of the player's hands against the opponent reach when a
+    // win pays `win` and a loss costs `lose`. Ties split the pot.
+    pub fn values(&self, player: Player, removal: &CardRemoval, opponent_reach: &[f64], win: f64, lose: f64) -> Vec<f64> {
+        let opponent = player.opponent();
+        let (p, o) = (player.as_usize(), opponent.as_usize());
+        let mut values = vec![0.0; self.strengths[p].len()];
+
+        // Opponent hands weaker than the hand
+        let mut sum = 0.0;
+        let mut card_sums = vec![0.0; removal.card_count()];
+        let mut j = 0;
+        for &h in self.order[p].iter() {
+            while j < self.order[o].len() && self.strengths[o][self.order[o][j]] < self.strengths[p][h] {
+                let opponent_hand = self.order[o][j];
+                sum += opponent_reach[opponent_hand];
+                CardRemoval::add(&mut card_sums, removal.hand_cards(opponent, opponent_hand), opponent_reach[opponent_hand]);
+                j += 1;
+            }
+
+            values[h] += win * removal.exclude(player, h, sum, &card_sums, None);
+        }
+
+        // Opponent hands stronger than the hand
+        let mut sum = 0.0;
+        let mut card_sums = vec![0.0; removal.card_count()];
+        let mut j = self.order[o].len();
+        for &h in self.order[p].iter().rev() {
+            while j > 0 && self.strengths[o][self.order[o][j - 1]] > self.strengths[p][h] {
+                let opponent_hand = self.order[o][j - 1];
+                sum += opponent_reach[opponent_hand];
+                CardRemoval::add(&mut card_sums, removal.hand_cards(opponent, opponent_hand), opponent_reach[opponent_hand]);
+                j -= 1;
+            }
+
+            values[h] -= lose * removal.exclude(player, h, sum, &card_sums, None);
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand_evaluator::player_wins;
+    use crate::card::Card;
+    use crate::suit::Suit;
+
+    fn hands(oop: &[&str], ip: &[&str]) -> [Vec<HoleCards>; 2] {
+        let to_hands = |strs: &[&str]| strs.iter().map(|s| HoleCards::new_from_string(s)).collect();
+        [to_hands(ip), to_hands(oop)]
+    }
+
+    #[test]
+    fn test_values_match_pairwise_showdowns() {
+        let board = Board::from_vec(vec![
+            Card::new(14, Suit::Diamonds),
+            Card::new(11, Suit::Clubs),
+            Card::new(2, Suit::Hearts),
+            Card::new(7, Suit::Spades),
+            Card::new(7, Suit::Diamonds),
+        ]);
+        let hands = hands(&["AhKh", "KsKd", "JsTs", "2s2c"], &["AsKs", "KhKc", "AcQc", "7c6c", "JdTd"]);
+        let removal = CardRemoval::new(&hands);
+        let showdown = Showdown::new(&hands, &board);
+        let reach = [1.0, 0.5, 0.25, 2.0, 3.0];
+
+        let values = showdown.values(Player::OOP, &removal, &reach, 2.0, 3.0);
+
+        for (h, hand) in hands[1].iter().enumerate() {
+            let mut expected = 0.0;
+            for (o, opponent) in hands[0].iter().enumerate() {
+                if hand.cards().iter().any(|card| opponent.cards().contains(card)) {
+                    continue;
+                }
+                expected += reach[o] * match player_wins(hand.clone(), opponent.clone(), board.clone()) {
+                    Some(true) => 2.0,
+                    Some(false) => -3.0,
+                    None => 0.0,
+                };
+            }
+
+            assert!((values[h] - expected).abs() < 1e-9, "{}: {} != {}", hand, values[h], expected);
+        }
+    }
+
+    #[test]
+    fn test_equal_hands_have_equal_strength() {
+        let board = Board::from_vec(vec![
+            Card::new(14, Suit::Diamonds),
+            Card::new(11, Suit::Clubs),
+            Card::new(2, Suit::Hearts),
+        ]);
+        let hands = hands(&["KsKd"], &["KhKc"]);
+        let showdown = Showdown::new(&hands, &board);
+
+        assert_eq!(showdown.strength(Player::OOP, 0), showdown.strength(Player::IP, 0));
+    }
+}
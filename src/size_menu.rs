@@ -0,0 +1,34 @@
+use crate::bet::Bet;
+
+// Bet sizes of one kind of line, e.g. donk bets
+#[derive(Clone, Debug, PartialEq)]
+pub enum SizeMenu {
+    // The street's bet sizes
+    Default,
+    Sizes(Vec<Bet>),
+    // No bets, the player can only check
+    Disabled,
+}
+
+impl SizeMenu {
+    pub fn sizes<'a>(&'a self, street_sizes: &'a Vec<Bet>) -> Option<&'a Vec<Bet>> {
+        match self {
+            SizeMenu::Default => Some(street_sizes),
+            SizeMenu::Sizes(sizes) => Some(sizes),
+            SizeMenu::Disabled => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes() {
+        let street_sizes = vec![Bet::P(50)];
+        assert_eq!(SizeMenu::Default.sizes(&street_sizes), Some(&street_sizes));
+        assert_eq!(SizeMenu::Sizes(vec![Bet::P(33)]).sizes(&street_sizes), Some(&vec![Bet::P(33)]));
+        assert_eq!(SizeMenu::Disabled.sizes(&street_sizes), None);
+    }
+}
@@ -2,39 +2,65 @@ use crate::info_state::InfoState;
 use crate::visitor::Visitor;
 use crate::node::Node;
 use crate::action::Action;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use crate::game_tree::GameTree;
 use crate::game::Game;
 use crate::utils::Utils;
 use crate::tree_walker::TreeWalker;
 use crate::player::Player;
+use crate::card::Card;
+use crate::hole_cards::HoleCards;
 
 pub struct StatisticsVisitor<'a, G: Game + Clone> {
-    stat_nodes: HashMap<InfoState, StatisticsNode>,
+    // Indexed by the info state index in the arena
+    stat_nodes: Vec<StatisticsNode>,
+    root: StatisticsNode,
+    br_utils: HashMap<Player, f64>,
     tree: &'a GameTree<G>,
 }
 
 impl<'a, G: Game + Clone> StatisticsVisitor<'a, G> {
     pub fn new(tree: &'a GameTree<G>) -> Self {
+        let mut stat_nodes = Vec::with_capacity(tree.arena.info_state_count);
+        for node in tree.arena.nodes.iter().filter(|node| !node.actions.is_empty()) {
+            for _ in tree.arena.hands(node.player) {
+                stat_nodes.push(StatisticsNode::new(node.actions.len()));
+            }
+        }
+
         StatisticsVisitor {
-            stat_nodes: HashMap::new(),
+            stat_nodes,
+            root: StatisticsNode::new(0),
+            br_utils: HashMap::new(),
             tree
         }
     }
 
     pub fn build(&mut self) {
         let game = self.tree.game.clone();
-        TreeWalker::walk_tree(&game, self);
+        let tree = self.tree;
+        TreeWalker::walk_tree(&game, &tree.arena, self);
 
-        let mut br_visitor = BestReponseVisitor::new(&mut self.stat_nodes, Player::OOP, self.tree);
-        TreeWalker::walk_tree(&game, &mut br_visitor);
+        for player in [Player::OOP, Player::IP] {
+            let mut best_response = BestResponse::new(&mut self.stat_nodes, &mut self.root, player, self.tree);
+            let br_util = best_response.walk(&game);
+            self.br_utils.insert(player, br_util);
+        }
+    }
 
-        // let mut br_visitor = BestReponseVisitor::new(&mut self.stat_nodes, Player::IP, self.tree);
-        // TreeWalker::walk_tree(&game, &mut br_visitor);
+    // The empty info state refers to the root of the game
+    fn stat_node(&self, info_state: &InfoState) -> &StatisticsNode {
+        if *info_state == InfoState::new_empty() {
+            return &self.root;
+        }
+
+        let index = self.tree.arena.info_index(info_state)
+            .unwrap_or_else(|| panic!("Info state not found: {}", info_state));
+        &self.stat_nodes[index]
     }
 
     pub fn node_util(&self, info_state: &InfoState) -> f64 {
-        let stat_node = self.stat_nodes.get(info_state).unwrap();
+        let stat_node = self.stat_node(info_state);
         if stat_node.visits == 0 {
             return 0.0;
         }
@@ -46,7 +72,7 @@ impl<'a, G: Game + Clone> StatisticsVisitor<'a, G> {
     }
 
     pub fn node_action_utils(&self, info_state: &InfoState) -> Vec<f64> {
-        let stat_node = self.stat_nodes.get(info_state).unwrap();
+        let stat_node = self.stat_node(info_state);
         if stat_node.visits == 0 {
             return vec![0.0; stat_node.action_util_sums.len()];
         }
@@ -54,8 +80,10 @@ impl<'a, G: Game + Clone> StatisticsVisitor<'a, G> {
         stat_node.action_util_sums.iter().map(|x| x * stat_node.reach_prob / stat_node.visits as f64).collect()
     }
 
+    // Util of the info state player when best responding from the info state on.
+    // The root info state holds the OOP best response.
     pub fn node_br_util(&self, info_state: &InfoState) -> f64 {
-        let stat_node = self.stat_nodes.get(info_state).unwrap();
+        let stat_node = self.stat_node(info_state);
         if stat_node.visits == 0 {
             return 0.0;
         }
@@ -66,18 +94,27 @@ impl<'a, G: Game + Clone> StatisticsVisitor<'a, G> {
         ev
     }
 
+    pub fn node_best_response(&self, info_state: &InfoState) -> Action {
+        self.stat_node(info_state).best_response.clone()
+    }
+
+    // Chips the info state player gains by best responding instead of following the strategy.
     pub fn node_exploitability(&self, info_state: &InfoState) -> f64 {
-        let br_util = self.node_br_util(info_state);
-        let util = self.node_util(info_state);
+        self.node_br_util(info_state) - self.node_util(info_state)
+    }
 
-        // BR util may be smaller than util if the node util
-        // because node util is weighted by reach probability.
-        // if info_state == &InfoState::new_empty() {
-        //     debug_assert!(br_util >= util,
-        //         "BR util should be greater than or equal to util: BR: {:.2}, Util: {:.2}", br_util, util);
-        // }
+    // Util of the player best responding to the opponent strategy over the whole game.
+    pub fn br_util(&self, player: Player) -> f64 {
+        self.br_utils[&player]
+    }
 
-        (br_util - util) / util * 100.0
+    // Average of both players best response utils in chips.
+    pub fn exploitability(&self) -> f64 {
+        (self.br_util(Player::OOP) + self.br_util(Player::IP)) / 2.0
+    }
+
+    pub fn exploitability_mbb(&self) -> f64 {
+        self.exploitability() / self.tree.game.big_blind() * 1000.0
     }
 }
 
@@ -85,24 +122,17 @@ impl<'a, G: Game + Clone> Visitor for StatisticsVisitor<'a, G> {
     fn visit_root_node(&mut self, info_state: &InfoState, util: f64) {
         debug_assert!(util.is_finite(), "Expected finite value for info state {:}, got: {:.2}", info_state, util);
 
-        let stat_node = self.stat_nodes.entry(info_state.clone()).or_insert(
-            StatisticsNode::new(0));
-
-        stat_node.rb_weighted_util_sum += util;
-        stat_node.visits += 1;
+        self.root.rb_weighted_util_sum += util;
+        self.root.visits += 1;
     }
 
 
     fn visit_action_node(&mut self, node: &Node) {
-        let stat_node = self.stat_nodes
-            .entry(node.info_state()).or_insert(
-                StatisticsNode::new(node.actions.len()));
-
-        update_node(stat_node, node);
+        update_node(&mut self.stat_nodes[node.info_index], node);
     }
 
     fn get_action_probs(&self, node: &Node) -> Vec<f64> {
-        self.tree.average_strategy(&node.info_state())
+        self.tree.node_average_strategy(node)
     }
 }
 
@@ -120,54 +150,152 @@ fn update_node(stat_node: &mut StatisticsNode, node: &Node) {
     }
 }
 
-struct BestReponseVisitor<'a, G: Game + Clone> {
-    stat_nodes: &'a mut HashMap<InfoState, StatisticsNode>,
+// Indexes of the parent nodes, nodes after the dealt card and their chance weights
+type CardGroup<'a> = (Vec<usize>, Vec<Node<'a>>, Vec<f64>);
+
+// Best response of one player against the average strategy of the opponent.
+// Nodes sharing the best responding player's info state are walked together,
+// so that actions are chosen per info state from counterfactual values.
+struct BestResponse<'a, G: Game + Clone> {
+    stat_nodes: &'a mut Vec<StatisticsNode>,
+    root: &'a mut StatisticsNode,
     player: Player,
     tree: &'a GameTree<G>,
 }
 
-impl<'a, G: Game + Clone> BestReponseVisitor<'a, G> {
-    pub fn new(stats: &'a mut HashMap<InfoState, StatisticsNode>, player: Player, tree: &'a GameTree<G>) -> Self {
-        BestReponseVisitor {
+impl<'a, G: Game + Clone> BestResponse<'a, G> {
+    pub fn new(stats: &'a mut Vec<StatisticsNode>, root: &'a mut StatisticsNode, player: Player, tree: &'a GameTree<G>) -> Self {
+        BestResponse {
             stat_nodes: stats,
+            root,
             player,
             tree,
         }
     }
-}
 
-impl<'a, G: Game + Clone> Visitor for BestReponseVisitor<'a, G> {
-    fn get_action_probs(&self, node: &Node) -> Vec<f64> {
-        if node.player == self.player {
-            let stat_node = self.stat_nodes.get(&node.info_state()).unwrap();
-            let action_utils = stat_node.action_util_sums.clone();
-            let i = Utils::arg_max(&action_utils);
-            let mut action_probs = vec![0.0; node.actions.len()];
-            action_probs[i] = 1.0;
-
-            action_probs
+    // Returns the deal weighted best response util of the player
+    pub fn walk(&mut self, game: &G) -> f64 {
+        let deals = game.generate_deals();
+        let mut deal_groups: BTreeMap<HoleCards, Vec<Node>> = BTreeMap::new();
+        for deal in deals.iter() {
+            let node = TreeWalker::root_node(game, &self.tree.arena, deal);
+            deal_groups.entry(node.cards.get(self.player)).or_default().push(node);
+        }
+
+        let mut util_sum = 0.0;
+        let mut weight_sum = 0.0;
+        for nodes in deal_groups.values() {
+            let values = self.node_values(game, nodes, &vec![1.0; nodes.len()]);
+            for (node, value) in nodes.iter().zip(values.iter()) {
+                let weight = node.player_reach_prob() * node.opponent_reach_prob();
+                util_sum += weight * value;
+                weight_sum += weight;
+            }
+        }
+
+        let br_util = if weight_sum > 0.0 { util_sum / weight_sum } else { 0.0 };
+        if self.player == Player::OOP {
+            self.root.br_util = br_util * self.root.visits as f64;
+        }
+
+        br_util
+    }
+
+    // Util of the player in each node, given the node was reached.
+    // Chance weights are the probabilities of the cards dealt on the way to each node.
+    fn node_values(&mut self, game: &G, nodes: &[Node], chance_weights: &[f64]) -> Vec<f64> {
+        let first = &nodes[0];
+        if first.is_terminal(game) {
+            nodes.iter().map(|node| self.payoff(game, node)).collect()
+        } else if first.is_street_completing_action() {
+            self.chance_values(game, nodes, chance_weights)
+        } else if first.player == self.player {
+            self.best_response_values(game, nodes, chance_weights)
         } else {
-            self.tree.average_strategy(&node.info_state())
+            self.opponent_values(game, nodes, chance_weights)
         }
     }
 
-    fn visit_action_node(&mut self, node: &Node) {
-        let stat_node = self.stat_nodes.get_mut(&node.info_state()).unwrap();
-        if node.player == self.player {
-            let reach_prob = node.player_reach_prob() * node.opponent_reach_prob();
+    fn payoff(&self, game: &G, node: &Node) -> f64 {
+        let payoff = TreeWalker::payoff(game, node);
+        if node.player == self.player { payoff } else { -payoff }
+    }
 
-            stat_node.br_util += node.util * reach_prob;
-            let i = Utils::arg_max(&stat_node.action_util_sums);
-            stat_node.best_response = node.actions[i].clone();
-        };
+    fn chance_values(&mut self, game: &G, nodes: &[Node], chance_weights: &[f64]) -> Vec<f64> {
+        // Nodes dealt the same card share the next public state
+        let mut card_groups: BTreeMap<Card, CardGroup> = BTreeMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            let card_prob = 1.0 / node.deck_len() as f64;
+            for card in node.deck() {
+                let group = card_groups.entry(card.clone()).or_default();
+                group.0.push(i);
+                group.1.push(node.next_street_node(&self.tree.arena, card));
+                group.2.push(chance_weights[i] * card_prob);
+            }
+        }
+
+        let mut values = vec![0.0; nodes.len()];
+        for (indexes, next_nodes, next_weights) in card_groups.values() {
+            let next_values = self.node_values(game, next_nodes, next_weights);
+            for (i, value) in indexes.iter().zip(next_values.iter()) {
+                values[*i] += value / nodes[*i].deck_len() as f64;
+            }
+        }
+
+        values
     }
 
-    fn visit_root_node(&mut self, info_state: &InfoState, util: f64) {
-        if self.player == Player::OOP {
-            let stat_node = self.stat_nodes.get_mut(&info_state).unwrap();
+    fn opponent_values(&mut self, game: &G, nodes: &[Node], chance_weights: &[f64]) -> Vec<f64> {
+        let strategies: Vec<Vec<f64>> = nodes.iter()
+            .map(|node| self.tree.node_average_strategy(node))
+            .collect();
+
+        let mut values = vec![0.0; nodes.len()];
+        for a in 0..nodes[0].actions.len() {
+            let next_nodes: Vec<Node> = nodes.iter().zip(strategies.iter())
+                .map(|(node, strategy)| node.next_action_node(&self.tree.arena, a, strategy[a]))
+                .collect();
+            let next_values = self.node_values(game, &next_nodes, chance_weights);
+
+            for i in 0..nodes.len() {
+                values[i] += strategies[i][a] * next_values[i];
+            }
+        }
 
-            stat_node.br_util += util;
+        values
+    }
+
+    fn best_response_values(&mut self, game: &G, nodes: &[Node], chance_weights: &[f64]) -> Vec<f64> {
+        // All nodes share the info state of the best responding player
+        let info_index = nodes[0].info_index;
+        let actions = nodes[0].actions.clone();
+        let strategy = self.tree.node_average_strategy(&nodes[0]);
+
+        // The player's reach follows the strategy so that the stored
+        // best response utils are weighted like the strategy utils.
+        let action_values: Vec<Vec<f64>> = (0..actions.len()).map(|a| {
+            let next_nodes: Vec<Node> = nodes.iter()
+                .map(|node| node.next_action_node(&self.tree.arena, a, strategy[a]))
+                .collect();
+            self.node_values(game, &next_nodes, chance_weights)
+        }).collect();
+
+        let counterfactual_values: Vec<f64> = action_values.iter().map(|values| {
+            values.iter().zip(nodes.iter()).zip(chance_weights.iter())
+                .map(|((value, node), weight)| value * node.opponent_reach_prob() * weight)
+                .sum()
+        }).collect();
+
+        let best = Utils::arg_max(&counterfactual_values);
+        let values = action_values[best].clone();
+
+        let stat_node = &mut self.stat_nodes[info_index];
+        stat_node.best_response = actions[best].clone();
+        for (node, value) in nodes.iter().zip(values.iter()) {
+            stat_node.br_util += value * node.player_reach_prob() * node.opponent_reach_prob();
         }
+
+        values
     }
 }
 
@@ -220,7 +348,8 @@ mod tests {
         // Create suboptimal strategy for player 1
         let cards2 = InfoState::new(Player::IP, HoleCards::new_with_ranks(2, 2),
             History::new_from_vec(vec![HistoryNode::Action(Action::Check)]));
-        *tree.strategy_sum.get_mut(&cards2).unwrap() = vec![0.0, 1.0];
+        let range = tree.buffer_range(&cards2);
+        tree.strategy_sum[range].copy_from_slice(&[0.0, 1.0]);
 
         tree
     }
@@ -234,11 +363,11 @@ mod tests {
         let info_state = InfoState::new_empty();
         let ideal_ev = -1.0/18.0;
 
-        debug_assert!((statistics_visitor.node_util(&info_state) - ideal_ev).abs() < 1e-6,
+        assert!((statistics_visitor.node_util(&info_state) - ideal_ev).abs() < 1e-6,
             "Expected: -1/18, got: {:.4}", statistics_visitor.node_util(&info_state));
-        debug_assert!((statistics_visitor.node_br_util(&info_state) - ideal_ev).abs() < 1e-6,
+        assert!((statistics_visitor.node_br_util(&info_state) - ideal_ev).abs() < 1e-6,
             "Expected: -1/18, got: {:.4}", statistics_visitor.node_br_util(&info_state));
-        debug_assert!(statistics_visitor.node_exploitability(&info_state) < 1e-6,
+        assert!(statistics_visitor.node_exploitability(&info_state) < 1e-6,
             "Expected 0 exploitability, got: {:.4}", statistics_visitor.node_exploitability(&info_state));
     }
 
@@ -254,12 +383,43 @@ mod tests {
         // Avg EV = (-1 + -1/3 + 5/3) / 3 = 1/9
 
         let info_state = InfoState::new_empty();
-        debug_assert!((statistics_visitor.node_util(&info_state) - -1.0/18.0).abs() < 1e-6,
-            "Expected: 1/18, got: {:.4}", statistics_visitor.node_util(&info_state));
-        debug_assert!((statistics_visitor.node_br_util(&info_state) - 1.0/9.0).abs() < 1e-6,
+        assert!((statistics_visitor.node_util(&info_state) - -1.0/18.0).abs() < 1e-6,
+            "Expected: -1/18, got: {:.4}", statistics_visitor.node_util(&info_state));
+        assert!((statistics_visitor.node_br_util(&info_state) - 1.0/9.0).abs() < 1e-6,
             "Expected: 1/9, got: {:.4}", statistics_visitor.node_br_util(&info_state));
-        debug_assert!(statistics_visitor.node_exploitability(&info_state) - 0.0 < 1e-6,
-            "Expected 0 exploitability, got: {:.4}", statistics_visitor.node_exploitability(&info_state));
+        assert!((statistics_visitor.node_exploitability(&info_state) - 1.0/6.0).abs() < 1e-6,
+            "Expected 1/6 exploitability, got: {:.4}", statistics_visitor.node_exploitability(&info_state));
+    }
+
+    #[test]
+    fn test_ideal_two_sided_exploitability() {
+        let tree = IdealKuhnBuilderVisitor::new().tree;
+        let mut statistics_visitor = StatisticsVisitor::new(&tree);
+        statistics_visitor.build();
+
+        debug_assert!((statistics_visitor.br_util(Player::OOP) - -1.0/18.0).abs() < 1e-6,
+            "Expected: -1/18, got: {:.4}", statistics_visitor.br_util(Player::OOP));
+        debug_assert!((statistics_visitor.br_util(Player::IP) - 1.0/18.0).abs() < 1e-6,
+            "Expected: 1/18, got: {:.4}", statistics_visitor.br_util(Player::IP));
+        debug_assert!(statistics_visitor.exploitability().abs() < 1e-6,
+            "Expected 0 exploitability, got: {:.4}", statistics_visitor.exploitability());
+    }
+
+    #[test]
+    fn test_exploitable_two_sided_exploitability() {
+        let tree = create_exploitable_IP_2_strategy();
+        let mut statistics_visitor = StatisticsVisitor::new(&tree);
+        statistics_visitor.build();
+
+        // OOP BR gains 1/9 - (-1/18) = 1/6 over the game value,
+        // OOP strategy is unchanged so IP BR gains nothing.
+        // Exploitability = (1/9 + 1/18) / 2 = 1/12
+        debug_assert!((statistics_visitor.br_util(Player::IP) - 1.0/18.0).abs() < 1e-6,
+            "Expected: 1/18, got: {:.4}", statistics_visitor.br_util(Player::IP));
+        debug_assert!((statistics_visitor.exploitability() - 1.0/12.0).abs() < 1e-6,
+            "Expected 1/12 exploitability, got: {:.4}", statistics_visitor.exploitability());
+        debug_assert!((statistics_visitor.exploitability_mbb() - 1000.0/12.0).abs() < 1e-3,
+            "Expected 83.33 mbb/hand, got: {:.4}", statistics_visitor.exploitability_mbb());
     }
 
     #[test]
@@ -318,8 +478,9 @@ mod tests {
             "Expected: 7/6, got: {:.4}", statistics_visitor.node_util(&info_state));
         debug_assert!((statistics_visitor.node_br_util(&info_state) - 5.0/3.0).abs() < 1e-6,
             "Expected: 5/3, got: {:.4}", statistics_visitor.node_br_util(&info_state));
-        debug_assert!(statistics_visitor.node_exploitability(&info_state) - 42.8571 < 1e-4,
-            "Expected 42.8571 exploitability, got: {:.4}", statistics_visitor.node_exploitability(&info_state));
+        debug_assert!((statistics_visitor.node_exploitability(&info_state) - 0.5).abs() < 1e-6,
+            "Expected 0.5 exploitability, got: {:.4}", statistics_visitor.node_exploitability(&info_state));
+        assert_eq!(statistics_visitor.node_best_response(&info_state), Action::Check);
     }
 
     #[test]
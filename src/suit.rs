@@ -68,6 +68,18 @@ impl Suit {
     pub fn from_char(c: char) -> Self {
         Self::from_str(&c.to_string())
     }
+
+    // Letter of range notation
+    pub fn to_char(&self) -> char {
+        match self {
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+            Suit::Diamonds => 'd',
+            Suit::Clubs => 'c',
+            Suit::Suited => 's',
+            Suit::Offsuit => 'o',
+        }
+    }
 }
 
 impl PartialOrd for Suit {
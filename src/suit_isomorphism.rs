@@ -0,0 +1,92 @@
+use crate::board::Board;
+use crate::card::Card;
+use crate::range::Range;
+use crate::suit::Suit;
+
+// Pairs of suits that can be exchanged without changing the game, because
+// the board of every street and both ranges map onto themselves. Cards
+// that differ only by such an exchange deal strategically identical
+// runouts. Only the range walker collapses them, for chance cards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuitIsomorphism {
+    pub swaps: Vec<(Suit, Suit)>,
+}
+
+impl SuitIsomorphism {
+    pub fn new(boards: &[Board], ranges: &[Range]) -> Self {
+        let suits = Suit::to_vec();
+        let mut swaps = Vec::new();
+        for (i, &a) in suits.iter().enumerate() {
+            for &b in suits[i + 1..].iter() {
+                if boards.iter().all(|board| board.is_suit_symmetric(a, b)) &&
+                    ranges.iter().all(|range| range.is_suit_symmetric(a, b)) {
+                    swaps.push((a, b));
+                }
+            }
+        }
+
+        SuitIsomorphism {
+            swaps,
+        }
+    }
+
+    pub fn is_trivial(&self) -> bool {
+        self.swaps.is_empty()
+    }
+
+    // First card before cards[i] that is isomorphic to it, and the index of
+    // the swap mapping one onto the other
+    pub fn isomorphic_card(&self, cards: &[Card], i: usize) -> Option<(usize, usize)> {
+        self.swaps.iter().enumerate()
+            .filter(|(_, (a, b))| cards[i].suit == *a || cards[i].suit == *b)
+            .filter_map(|(s, (a, b))| {
+                let card = cards[i].swap_suits(*a, *b);
+                cards[..i].iter().position(|c| *c == card).map(|j| (j, s))
+            })
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(cards: &[(u8, Suit)]) -> Board {
+        Board::from_vec(cards.iter().map(|(rank, suit)| Card::new(*rank, *suit)).collect())
+    }
+
+    #[test]
+    fn test_monotone_flop() {
+        let flop = board(&[(14, Suit::Spades), (11, Suit::Spades), (2, Suit::Spades)]);
+        let ranges = [Range::new_from_string("QQ"), Range::new_from_string("KK;AKs")];
+        let isomorphism = SuitIsomorphism::new(&[flop], &ranges);
+
+        assert_eq!(isomorphism.swaps, vec![
+            (Suit::Hearts, Suit::Diamonds),
+            (Suit::Hearts, Suit::Clubs),
+            (Suit::Diamonds, Suit::Clubs),
+        ]);
+
+        let cards = vec![Card::new(5, Suit::Hearts), Card::new(5, Suit::Spades), Card::new(5, Suit::Clubs)];
+        assert_eq!(isomorphism.isomorphic_card(&cards, 0), None);
+        assert_eq!(isomorphism.isomorphic_card(&cards, 1), None);
+        assert_eq!(isomorphism.isomorphic_card(&cards, 2), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_rainbow_flop_has_no_swaps() {
+        let flop = board(&[(14, Suit::Spades), (11, Suit::Hearts), (2, Suit::Diamonds)]);
+        let ranges = [Range::new_from_string("QQ"), Range::new_from_string("KK")];
+
+        assert!(SuitIsomorphism::new(&[flop], &ranges).is_trivial());
+    }
+
+    #[test]
+    fn test_asymmetric_range() {
+        let flop = board(&[(14, Suit::Spades), (11, Suit::Spades), (2, Suit::Spades)]);
+        let ranges = [Range::new_from_string("QQ"), Range::new_from_string("KhKd")];
+        let isomorphism = SuitIsomorphism::new(&[flop], &ranges);
+
+        assert_eq!(isomorphism.swaps, vec![(Suit::Hearts, Suit::Diamonds)]);
+    }
+}
@@ -0,0 +1,24 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrainingMethod {
+    // Sample one deal per iteration and walk every action for both players
+    MonteCarlo,
+    // Walk every deal and chance card per iteration
+    Full,
+    // Sample one deal, chance and opponent actions per iteration. The traverser
+    // explores all of their own actions and alternates between IP and OOP.
+    ExternalSampling,
+    // Sample a single action at every decision node. The traverser samples from
+    // the strategy mixed with uniform exploration of weight epsilon.
+    OutcomeSampling { epsilon: f64 },
+    // Walk the public tree with reach vectors over the hands of both players
+    // and update them alternately. Runs on one thread and needs games with
+    // distinct cards.
+    RangeVsRange,
+}
+
+impl TrainingMethod {
+    // Exploration recommended by Lanctot et al.
+    pub fn new_outcome_sampling_default() -> Self {
+        TrainingMethod::OutcomeSampling { epsilon: 0.6 }
+    }
+}
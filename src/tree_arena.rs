@@ -0,0 +1,216 @@
+use crate::game::Game;
+use crate::history::History;
+use crate::hole_cards::HoleCards;
+use crate::info_state::InfoState;
+use crate::node::Node;
+use crate::player::Player;
+use crate::card::Card;
+use crate::action::Action;
+use crate::tree_node::TreeNode;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
+// Explicit public game tree. Nodes refer to their children by index, and the
+// regrets and strategy sums of all info states live in contiguous buffers
+// laid out by (node, hand, action).
+#[derive(Clone, Debug)]
+pub struct TreeArena {
+    pub nodes: Vec<TreeNode>,
+    // Hands of each player, by Player::as_usize
+    hands: [Vec<HoleCards>; 2],
+    hand_indices: [HashMap<HoleCards, usize>; 2],
+    // Only used to look up info states, walks follow child indices
+    node_indices: HashMap<History, usize>,
+    pub buffer_len: usize,
+    pub info_state_count: usize,
+}
+
+impl TreeArena {
+    pub fn new_empty() -> Self {
+        TreeArena {
+            nodes: Vec::new(),
+            hands: [Vec::new(), Vec::new()],
+            hand_indices: [HashMap::new(), HashMap::new()],
+            node_indices: HashMap::new(),
+            buffer_len: 0,
+            info_state_count: 0,
+        }
+    }
+
+    pub fn new<G: Game>(game: &G) -> Self {
+        let mut arena = Self::new_empty();
+        let deals = game.generate_deals();
+
+        for player in [Player::IP, Player::OOP] {
+            let hands: BTreeSet<HoleCards> = deals.iter().map(|deal| deal.cards.get(player)).collect();
+            arena.hand_indices[player.as_usize()] = hands.iter().cloned().enumerate()
+                .map(|(i, hand)| (hand, i))
+                .collect();
+            arena.hands[player.as_usize()] = hands.into_iter().collect();
+        }
+
+        // Duplicate cards, e.g. in Leduc, deal the same public node
+        let mut deck: Vec<Card> = Vec::new();
+        for card in game.deck().iter() {
+            if !deck.contains(card) {
+                deck.push(card.clone());
+            }
+        }
+
+        let root = deals.first().map(|deal| deal.history.clone()).unwrap_or_else(History::new);
+        debug_assert!(deals.iter().all(|deal| deal.history == root), "Deals must start from the same history");
+        arena.add_node(game, root, game.first_player(), game.legal_first_actions(), &deck);
+
+        arena
+    }
+
+    fn add_node<G: Game>(&mut self, game: &G, history: History, player: Player, actions: Vec<Action>, deck: &[Card]) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(TreeNode::new(history.clone(), player));
+        self.node_indices.insert(history.clone(), index);
+
+        if game.is_terminal(&history) {
+            return index;
+        }
+
+        if history.is_completing_action() {
+            let board = history.street().board();
+            for card in deck.iter().filter(|card| !board.cards.contains(card)) {
+                let mut next_history = history.clone();
+                next_history.push_street(history.street().next_street(card.clone()));
+                let next_actions = game.legal_actions(&next_history);
+
+                let child = self.add_node(game, next_history, Player::OOP, next_actions, deck);
+                self.nodes[index].children.push(child);
+                self.nodes[index].cards.push(card.clone());
+            }
+        } else {
+            let hands = self.hands[player.as_usize()].len();
+            self.nodes[index].offset = self.buffer_len;
+            self.nodes[index].info_offset = self.info_state_count;
+            self.buffer_len += hands * actions.len();
+            self.info_state_count += hands;
+
+            for action in actions.iter() {
+                let mut next_history = history.clone();
+                next_history.push_action(action.clone());
+                let next_actions = game.legal_actions(&next_history);
+
+                let child = self.add_node(game, next_history, player.opponent(), next_actions, deck);
+                self.nodes[index].children.push(child);
+            }
+            self.nodes[index].actions = actions;
+        }
+
+        index
+    }
+
+    pub fn hands(&self, player: Player) -> &[HoleCards] {
+        &self.hands[player.as_usize()]
+    }
+
+    pub fn hand_index(&self, player: Player, hole_cards: &HoleCards) -> Option<usize> {
+        self.hand_indices[player.as_usize()].get(hole_cards).copied()
+    }
+
+    pub fn node_index(&self, history: &History) -> Option<usize> {
+        self.node_indices.get(history).copied()
+    }
+
+    // Index of the info state among all info states of the tree
+    pub fn info_index(&self, info_state: &InfoState) -> Option<usize> {
+        let (node, hand) = self.find(info_state)?;
+        Some(node.info_offset + hand)
+    }
+
+    // Position of the info state's regrets and strategy sums in the buffers
+    pub fn buffer_range(&self, info_state: &InfoState) -> Option<Range<usize>> {
+        let (node, hand) = self.find(info_state)?;
+        let start = node.offset + hand * node.actions.len();
+        Some(start..start + node.actions.len())
+    }
+
+    fn find(&self, info_state: &InfoState) -> Option<(&TreeNode, usize)> {
+        let node = &self.nodes[self.node_index(&info_state.history)?];
+        if node.actions.is_empty() || node.player != info_state.player {
+            return None;
+        }
+
+        Some((node, self.hand_index(info_state.player, &info_state.hole_cards)?))
+    }
+
+    // Places a node dealt from the root of the tree
+    pub fn locate_root(&self, node: &mut Node) {
+        for player in [Player::IP, Player::OOP] {
+            let hole_cards = node.cards.get(player);
+            node.hand_indices[player.as_usize()] = self.hand_index(player, &hole_cards)
+                .unwrap_or_else(|| panic!("Hole cards {} of {} are not in the game tree", hole_cards, player));
+        }
+
+        self.locate(node, 0);
+    }
+
+    pub fn locate(&self, node: &mut Node, index: usize) {
+        let tree_node = &self.nodes[index];
+        let hand = node.hand_indices[node.player.as_usize()];
+        node.index = index;
+        node.offset = tree_node.offset + hand * tree_node.actions.len();
+        node.info_index = tree_node.info_offset + hand;
+    }
+
+    pub fn action_child(&self, node: &Node, action: usize) -> usize {
+        self.nodes[node.index].children[action]
+    }
+
+    pub fn chance_child(&self, node: &Node, card: &Card) -> usize {
+        self.nodes[node.index].chance_child(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuhn::Kuhn;
+    use crate::leduc::Leduc;
+    use crate::bet::Bet;
+
+    #[test]
+    fn test_kuhn_arena() {
+        let arena = TreeArena::new(&Kuhn::new());
+
+        // Root, X, B, XX, XB, BF, BC, XBF, XBC
+        assert_eq!(arena.nodes.len(), 9);
+        assert_eq!(arena.info_state_count, 12);
+        assert_eq!(arena.buffer_len, 24);
+        assert_eq!(arena.hands(Player::OOP).len(), 3);
+    }
+
+    #[test]
+    fn test_info_state_lookup() {
+        let arena = TreeArena::new(&Kuhn::new());
+        let mut history = History::new();
+        history.push_action(Action::Check);
+        history.push_action(Action::Bet(Bet::P(50)));
+
+        let info_state = InfoState::new(Player::OOP, HoleCards::new_with_ranks(2, 2), history.clone());
+        let node = &arena.nodes[arena.node_index(&history).unwrap()];
+        assert_eq!(arena.buffer_range(&info_state), Some(node.offset + 2..node.offset + 4));
+        assert_eq!(arena.info_index(&info_state), Some(node.info_offset + 1));
+
+        let wrong_player = InfoState::new(Player::IP, HoleCards::new_with_ranks(2, 2), history);
+        assert_eq!(arena.buffer_range(&wrong_player), None);
+    }
+
+    #[test]
+    fn test_leduc_chance_children() {
+        let arena = TreeArena::new(&Leduc::new());
+        let mut history = History::new();
+        history.push_action(Action::Check);
+        history.push_action(Action::Check);
+
+        let node = &arena.nodes[arena.node_index(&history).unwrap()];
+        assert_eq!(node.cards.len(), 3);
+        assert!(node.actions.is_empty());
+        assert_eq!(arena.nodes[node.children[0]].history.street().board().cards, vec![node.cards[0].clone()]);
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Regrets or strategy sums laid out like the game tree arena
+pub type Table = Vec<f64>;
+
+const MAGIC: &[u8; 4] = b"RSGT";
+pub const FORMAT_VERSION: u32 = 2;
+
+// Binary file holding the regret and strategy tables of a game tree.
+// Layout: magic, format version, game signature, regrets, strategy sums.
+// All integers and floats are little endian. The tables follow the arena
+// layout, which the game signature determines.
+pub struct TreeFile {}
+
+impl TreeFile {
+    pub fn save(path: &Path, signature: &str, regrets: &Table, strategy_sum: &Table) -> io::Result<()> {
+        let mut writer = TreeFileWriter::new();
+        writer.header(MAGIC, signature);
+        writer.table(regrets);
+        writer.table(strategy_sum);
+
+        fs::write(path, writer.bytes)
+    }
+
+    // Fails if the file was written by another format version or for a game
+    // with a different signature.
+    pub fn load(path: &Path, signature: &str) -> io::Result<(Table, Table)> {
+        let bytes = fs::read(path)?;
+        let mut reader = TreeFileReader::new(&bytes);
+        reader.header(MAGIC, signature)?;
+        let regrets = reader.table()?;
+        let strategy_sum = reader.table()?;
+
+        if !reader.is_empty() {
+            return Err(invalid_data("Trailing bytes after strategy tables".to_string()));
+        }
+
+        Ok((regrets, strategy_sum))
+    }
+}
+
+pub fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[derive(Default)]
+pub struct TreeFileWriter {
+    pub bytes: Vec<u8>,
+}
+
+impl TreeFileWriter {
+    pub fn new() -> Self {
+        TreeFileWriter {
+            bytes: Vec::new(),
+        }
+    }
+
+    pub fn header(&mut self, magic: &[u8; 4], signature: &str) {
+        self.bytes.extend_from_slice(magic);
+        self.u32(FORMAT_VERSION);
+        self.string(signature);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    pub fn table(&mut self, table: &Table) {
+        self.u64(table.len() as u64);
+        for value in table.iter() {
+            self.f64(*value);
+        }
+    }
+}
+
+pub struct TreeFileReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> TreeFileReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        TreeFileReader {
+            bytes,
+            position: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    pub fn header(&mut self, magic: &[u8; 4], signature: &str) -> io::Result<()> {
+        if self.take(magic.len())? != magic {
+            return Err(invalid_data(format!("Not a {} file", String::from_utf8_lossy(magic))));
+        }
+
+        let version = self.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported format version {}, expected {}", version, FORMAT_VERSION)));
+        }
+
+        let file_signature = self.string()?;
+        if file_signature != signature {
+            return Err(invalid_data(format!(
+                "Game config mismatch: file was solved for '{}', got '{}'", file_signature, signature)));
+        }
+
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() - self.position < len {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Game tree file is truncated"));
+        }
+
+        let slice = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        Ok(slice)
+    }
+
+    pub fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn f64(&mut self) -> io::Result<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn string(&mut self) -> io::Result<String> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| invalid_data(e.to_string()))
+    }
+
+    pub fn table(&mut self) -> io::Result<Table> {
+        let len = self.u64()? as usize;
+        if len > (self.bytes.len() - self.position) / 8 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Game tree file is truncated"));
+        }
+
+        let mut table = Vec::with_capacity(len);
+        for _ in 0..len {
+            table.push(self.f64()?);
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_round_trip() {
+        let table = vec![0.25, -1.5, 3.0, 1.0];
+
+        let mut writer = TreeFileWriter::new();
+        writer.table(&table);
+        let mut reader = TreeFileReader::new(&writer.bytes);
+
+        assert_eq!(reader.table().unwrap(), table);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_header_rejects_other_signature() {
+        let mut writer = TreeFileWriter::new();
+        writer.header(MAGIC, "Kuhn");
+
+        assert!(TreeFileReader::new(&writer.bytes).header(MAGIC, "Kuhn").is_ok());
+        let err = TreeFileReader::new(&writer.bytes).header(MAGIC, "Leduc").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_header_rejects_other_version() {
+        let mut writer = TreeFileWriter::new();
+        writer.bytes.extend_from_slice(MAGIC);
+        writer.u32(FORMAT_VERSION - 1);
+        writer.string("Kuhn");
+
+        let err = TreeFileReader::new(&writer.bytes).header(MAGIC, "Kuhn").unwrap_err();
+        assert!(err.to_string().contains("version"));
+    }
+
+    #[test]
+    fn test_header_rejects_other_magic() {
+        let mut writer = TreeFileWriter::new();
+        writer.header(b"RSCP", "Kuhn");
+
+        let err = TreeFileReader::new(&writer.bytes).header(MAGIC, "Kuhn").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_truncated_table() {
+        let mut writer = TreeFileWriter::new();
+        writer.table(&vec![0.25, 0.5]);
+        writer.bytes.pop();
+
+        let err = TreeFileReader::new(&writer.bytes).table().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
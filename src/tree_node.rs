@@ -0,0 +1,41 @@
+use crate::history::History;
+use crate::player::Player;
+use crate::action::Action;
+use crate::card::Card;
+
+// Public node of the game tree arena, shared by all hands.
+#[derive(Clone, Debug)]
+pub struct TreeNode {
+    pub history: History,
+    pub player: Player,
+    // Empty at terminal and street completing nodes
+    pub actions: Vec<Action>,
+    // Child of each action, or of each dealt card at street completing nodes
+    pub children: Vec<usize>,
+    // Card dealt to reach each child of a street completing node
+    pub cards: Vec<Card>,
+    // Start of the node's regrets and strategy sums, one row of actions per hand
+    pub offset: usize,
+    // Info state index of the player's first hand
+    pub info_offset: usize,
+}
+
+impl TreeNode {
+    pub fn new(history: History, player: Player) -> Self {
+        TreeNode {
+            history,
+            player,
+            actions: Vec::new(),
+            children: Vec::new(),
+            cards: Vec::new(),
+            offset: 0,
+            info_offset: 0,
+        }
+    }
+
+    pub fn chance_child(&self, card: &Card) -> usize {
+        let i = self.cards.iter().position(|c| c == card)
+            .unwrap_or_else(|| panic!("No child for card {} after {}", card, self.history));
+        self.children[i]
+    }
+}
@@ -37,7 +37,7 @@ impl<'a, G: Game + Clone> Visitor for TreePrintVisitor<'a, G> {
 
     fn visit_action_node(&mut self, node: &Node) {
         let actions = &node.actions;
-        let strategy = self.tree.average_strategy(&node.info_state());
+        let strategy = self.tree.node_average_strategy(node);
 
         let mut line = String::new();
         line.push_str("[");
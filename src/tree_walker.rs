@@ -3,19 +3,30 @@ use crate::info_state::InfoState;
 use crate::node::Node;
 use crate::player::Player;
 use crate::visitor::Visitor;
-use rand::rngs::StdRng;
+use crate::utils::Utils;
+use crate::deal::Deal;
+use crate::tree_arena::TreeArena;
+use rand_chacha::ChaCha12Rng;
 use rand::SeedableRng;
 
 pub struct TreeWalker;
 
 impl TreeWalker {
-    pub fn walk_tree<G: Game, V: Visitor>(game: &G, visitor: &mut V) -> f64 {
+    // Every deal and chance card is walked, suit isomorphic ones included.
+    // Their info states are per hole cards, so collapsing them would need the
+    // regrets of the isomorphic hands folded together.
+    pub fn walk_tree<G: Game, V: Visitor>(game: &G, arena: &TreeArena, visitor: &mut V) -> f64 {
+        Self::walk_deals(game, arena, &game.generate_deals(), visitor)
+    }
+
+    // Walks the full tree of the given deals, e.g. a thread's share of the deals
+    pub fn walk_deals<G: Game, V: Visitor>(game: &G, arena: &TreeArena, deals: &[Deal], visitor: &mut V) -> f64 {
         let mut ev = 0.0;
         let mut deal_count = 0;
-        let rng = &mut StdRng::seed_from_u64(0);
-        game.generate_deals().iter().for_each(|deal| {
-            let node = Node::new(game, deal.clone());
-            let node_ev = Self::iterate_tree(game, node, rng, &WalkMethod::Full, visitor);
+        let rng = &mut ChaCha12Rng::seed_from_u64(0);
+        deals.iter().for_each(|deal| {
+            let node = Self::root_node(game, arena, deal);
+            let node_ev = Self::iterate_tree(game, arena, node, rng, &WalkMethod::Full, visitor);
             ev += node_ev;
             deal_count += 1;
 
@@ -25,16 +36,45 @@ impl TreeWalker {
         return ev / deal_count as f64;
     }
 
-    pub fn monte_carlo_iterate<G: Game, V: Visitor>(game: &G, rng: &mut StdRng, visitor: &mut V) -> f64 {
+    pub fn monte_carlo_iterate<G: Game, V: Visitor>(game: &G, arena: &TreeArena, rng: &mut ChaCha12Rng, visitor: &mut V) -> f64 {
         let deal = game.deal(rng);
-        let node = Node::new(game, deal);
-        let node_ev = Self::iterate_tree(game, node, rng, &WalkMethod::MonteCarlo, visitor);
+        let node = Self::root_node(game, arena, &deal);
+        let node_ev = Self::iterate_tree(game, arena, node, rng, &WalkMethod::MonteCarlo, visitor);
         visitor.visit_root_node(&InfoState::new_empty(), node_ev);
 
         return node_ev;
     }
 
-    fn iterate_tree<G: Game, V: Visitor>(game: &G, mut node: Node, rng: &mut StdRng, method: &WalkMethod, visitor: &mut V) -> f64 {
+    // Traverser explores all of their actions while opponent actions
+    // and chance are sampled from the current strategy.
+    pub fn external_sampling_iterate<G: Game, V: Visitor>(game: &G, arena: &TreeArena, rng: &mut ChaCha12Rng, traverser: Player, visitor: &mut V) -> f64 {
+        let deal = game.deal(rng);
+        let node = Self::root_node(game, arena, &deal);
+        let node_ev = Self::iterate_tree(game, arena, node, rng, &WalkMethod::ExternalSampling(traverser), visitor);
+        visitor.visit_root_node(&InfoState::new_empty(), node_ev);
+
+        node_ev
+    }
+
+    // Samples a single action at every decision node. Traverser actions are
+    // sampled from the strategy mixed with epsilon exploration.
+    pub fn outcome_sampling_iterate<G: Game, V: Visitor>(game: &G, arena: &TreeArena, rng: &mut ChaCha12Rng, traverser: Player, epsilon: f64, visitor: &mut V) -> f64 {
+        let deal = game.deal(rng);
+        let node = Self::root_node(game, arena, &deal);
+        let node_ev = Self::iterate_tree(game, arena, node, rng, &WalkMethod::OutcomeSampling(traverser, epsilon), visitor);
+        visitor.visit_root_node(&InfoState::new_empty(), node_ev);
+
+        node_ev
+    }
+
+    pub fn root_node<'a, G: Game>(game: &G, arena: &'a TreeArena, deal: &'a Deal) -> Node<'a> {
+        let mut node = Node::new(game, deal);
+        arena.locate_root(&mut node);
+
+        node
+    }
+
+    fn iterate_tree<G: Game, V: Visitor>(game: &G, arena: &TreeArena, mut node: Node, rng: &mut ChaCha12Rng, method: &WalkMethod, visitor: &mut V) -> f64 {
         if node.is_terminal(game) {
             visitor.visit_terminal_node(&node);
 
@@ -46,53 +86,80 @@ impl TreeWalker {
             // node util from next action is positive.
             let sign = if node.player == Player::IP { -1.0 } else { 1.0 };
             match method {
-                WalkMethod::MonteCarlo => {
-                    let card = node.deck.draw().unwrap();
-                    let next_street = node.history.street().next_street(card.clone());
-                    let next_node = node.next_street_node(game, next_street);
+                WalkMethod::MonteCarlo | WalkMethod::ExternalSampling(_) | WalkMethod::OutcomeSampling(_, _) => {
+                    let card = node.draw().unwrap();
+                    let next_node = node.next_street_node(arena, &card);
 
-                    return sign * Self::iterate_tree(game, next_node, rng, method, visitor);
+                    return sign * Self::iterate_tree(game, arena, next_node, rng, method, visitor);
                 }
                 WalkMethod::Full => {
-                    for card in node.deck.iter() {
-                        let next_street = node.history.street().next_street(card.clone());
-                        let next_node = node.next_street_node(game, next_street);
-                        node.util += sign * Self::iterate_tree(game, next_node, rng, method, visitor);
+                    for card in node.deck() {
+                        let next_node = node.next_street_node(arena, card);
+                        node.util += sign * Self::iterate_tree(game, arena, next_node, rng, method, visitor);
                     }
 
-                    return node.util / node.deck.len() as f64;
+                    return node.util / node.deck_len() as f64;
                 }
             }
         } else {
             node.action_probs = visitor.get_action_probs(&node);
 
-            // TODO: implement walk methods
-            for i in 0..node.actions.len() {
-                let next_node = node.next_action_node(
-                    game,
-                    node.actions[i].clone(),
-                    node.action_probs[i]);
+            match method {
+                WalkMethod::ExternalSampling(traverser) | WalkMethod::OutcomeSampling(traverser, _)
+                        if node.player != *traverser => {
+                    // Sampling accounts for the opponent reach, so it is not multiplied in.
+                    let i = Utils::choose(&node.action_probs, rng);
+                    let next_node = node.next_action_node(arena, i, 1.0);
 
-                node.action_utils[i] = -Self::iterate_tree(game, next_node, rng, method, visitor);
-                node.util += node.action_probs[i] * node.action_utils[i];
-            }
+                    node.action_utils[i] = -Self::iterate_tree(game, arena, next_node, rng, method, visitor);
+                    node.util = node.action_utils[i];
 
-            visitor.visit_action_node(&node);
+                    visitor.visit_sampled_action_node(&node);
+                }
+                WalkMethod::OutcomeSampling(_, epsilon) => {
+                    let uniform = 1.0 / node.actions.len() as f64;
+                    let sample_probs: Vec<f64> = node.action_probs.iter()
+                        .map(|prob| epsilon * uniform + (1.0 - epsilon) * prob)
+                        .collect();
+                    let i = Utils::choose(&sample_probs, rng);
+                    let mut next_node = node.next_action_node(arena, i, node.action_probs[i]);
+                    next_node.sample_prob *= sample_probs[i];
+
+                    // Unsampled actions keep zero utility, the sampled one is importance weighted.
+                    node.action_utils[i] = -Self::iterate_tree(game, arena, next_node, rng, method, visitor) / sample_probs[i];
+                    node.util = node.action_probs[i] * node.action_utils[i];
+
+                    visitor.visit_traverser_node(&node);
+                }
+                _ => {
+                    for i in 0..node.actions.len() {
+                        let next_node = node.next_action_node(arena, i, node.action_probs[i]);
+
+                        node.action_utils[i] = -Self::iterate_tree(game, arena, next_node, rng, method, visitor);
+                        node.util += node.action_probs[i] * node.action_utils[i];
+                    }
+
+                    match method {
+                        WalkMethod::ExternalSampling(_) => visitor.visit_traverser_node(&node),
+                        _ => visitor.visit_action_node(&node),
+                    }
+                }
+            }
 
             debug_assert!(node.util.is_finite(), "Node util in info state {} is not finite", node.info_state());
             return node.util;
         }
     }
 
-    fn payoff<G: Game>(game: &G, node: &Node) -> f64 {
-        let won = game.player_wins(&node);
-        let win_amount = node.pot.payoff(node.player, won);
-
-        return win_amount;
+    pub fn payoff<G: Game>(game: &G, node: &Node) -> f64 {
+        game.payoff(node)
     }
 }
 
 enum WalkMethod {
     MonteCarlo,
     Full,
+    ExternalSampling(Player),
+    // Traverser and exploration epsilon
+    OutcomeSampling(Player, f64),
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 use rand::Rng;
 
 pub struct Utils {}
@@ -18,18 +18,31 @@ impl Utils {
         max_index
     }
 
-    pub fn choose(vec: &Vec<f64>, rng: &mut StdRng) -> usize {
+    pub fn choose(vec: &Vec<f64>, rng: &mut ChaCha12Rng) -> usize {
         debug_assert!(vec.iter().sum::<f64>() - 1.0 < 1e-6,
             "Probabilities do not sum to 1.0: {:?}", vec);
         let mut sample = rng.gen_range(0.0..1.0);
-        let mut i = 0;
 
-        while sample > 0.0 {
-            sample -= vec[i];
-            i += 1;
+        for (i, prob) in vec.iter().enumerate() {
+            if sample < *prob {
+                return i;
+            }
+            sample -= prob;
         }
 
-        i - 1
+        // Rounding left the sample past the last bucket
+        vec.iter().rposition(|prob| *prob > 0.0).unwrap()
+    }
+
+    pub fn regret_matching(regrets: &[f64]) -> Vec<f64> {
+        let positive_regrets: Vec<f64> = regrets.iter().map(|r| r.max(0.0)).collect();
+        let normalizing_sum: f64 = positive_regrets.iter().sum();
+
+        if normalizing_sum > 0.0 {
+            positive_regrets.iter().map(|r| r / normalizing_sum).collect()
+        } else {
+            vec![1.0 / regrets.len() as f64; regrets.len()]
+        }
     }
 }
 
@@ -44,10 +57,22 @@ mod tests {
         assert_eq!(Utils::arg_max(&vec), 2);
     }
 
+    #[test]
+    fn test_regret_matching() {
+        let regrets = vec![3.0, -2.0, 1.0];
+        assert_eq!(Utils::regret_matching(&regrets), vec![0.75, 0.0, 0.25]);
+    }
+
+    #[test]
+    fn test_regret_matching_without_positive_regrets() {
+        let regrets = vec![-3.0, 0.0, -1.0, -1.0];
+        assert_eq!(Utils::regret_matching(&regrets), vec![0.25; 4]);
+    }
+
     #[test]
     fn test_choose_normal_case() {
         let weights = vec![0.1, 0.2, 0.3, 0.4];
-        let mut rng = StdRng::seed_from_u64(42); // Fixed seed for reproducibility
+        let mut rng = ChaCha12Rng::seed_from_u64(42); // Fixed seed for reproducibility
 
         // Test multiple times to ensure the distribution is correct
         let mut counts = vec![0; weights.len()];
@@ -67,7 +92,7 @@ mod tests {
     #[test]
     fn test_choose_single_element() {
         let weights = vec![1.0];
-        let mut rng = StdRng::seed_from_u64(42);
+        let mut rng = ChaCha12Rng::seed_from_u64(42);
 
         // The only possible result is index 0
         assert_eq!(Utils::choose(&weights, &mut rng), 0);
@@ -76,7 +101,7 @@ mod tests {
     #[test]
     fn test_choose_zero_weights() {
         let weights = vec![0.0, 0.0, 1.0, 0.0];
-        let mut rng = StdRng::seed_from_u64(42);
+        let mut rng = ChaCha12Rng::seed_from_u64(42);
 
         // The only possible result is index 2
         assert_eq!(Utils::choose(&weights, &mut rng), 2);
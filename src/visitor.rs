@@ -6,7 +6,11 @@ pub trait Visitor {
     fn visit_terminal_node(&mut self, _: &Node) {}
     fn visit_street_completing_node(&mut self, _: &Node) {}
     fn visit_action_node(&mut self, _: &Node) {}
+    // Node of the traversing player in sampled walks. All actions were explored.
+    fn visit_traverser_node(&mut self, _: &Node) {}
+    // Node of the non-traversing player in sampled walks. One action was sampled.
+    fn visit_sampled_action_node(&mut self, _: &Node) {}
     fn get_action_probs(&self, node: &Node) -> Vec<f64> {
         vec![1.0; node.actions.len()]
     }
-}
\ No newline at end of file
+}
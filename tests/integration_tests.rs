@@ -1,9 +1,20 @@
 use rustysolver::cfr::CFR;
+use rustysolver::cfr_config::CfrConfig;
+use rustysolver::cfr_variant::CfrVariant;
+use rustysolver::training_method::TrainingMethod;
 use rustysolver::info_state::InfoState;
+use rustysolver::game_tree::GameTree;
+use rustysolver::checkpoint_config::CheckpointConfig;
+use rustysolver::checkpoint_interval::CheckpointInterval;
 use rustysolver::kuhn::Kuhn;
 use rustysolver::leduc::Leduc;
 use rustysolver::postflop_holdem::PostflopHoldem;
 use rustysolver::postflop_holdem_config::PostflopHoldemConfig;
+use rustysolver::limit_holdem::LimitHoldem;
+use rustysolver::limit_holdem_config::LimitHoldemConfig;
+use rustysolver::board::Board;
+use rustysolver::range::Range;
+use rustysolver::player::Player;
 
 #[test]
 fn test_kuhn_poker_ev() {
@@ -27,15 +38,271 @@ fn test_kuhn_poker_ev() {
     let br_util = statistics.node_br_util(&InfoState::new_empty());
     let br_util_diff = (br_util - ideal_ev).abs();
 
-    debug_assert!(br_util_diff < 0.01,
+    // The best response gains OOP's exploitability over the game value.
+    // Exploitability below 10 mbb averages both players, so one side may gain 0.02.
+    debug_assert!(br_util_diff < 0.02,
         "BR Util: {:.4}, Ideal: {:.4}, Diff: {:.4}",
         br_util, ideal_ev, br_util_diff);
 
-    let exploitability = statistics.node_exploitability(&InfoState::new_empty());
-    debug_assert!(exploitability < 15.0, "Exploitability: {:.4} %", exploitability);
+    let exploitability = statistics.exploitability_mbb();
+    debug_assert!(exploitability < 10.0, "Exploitability: {:.4} mbb/hand", exploitability);
 
 }
 
+#[test]
+fn test_kuhn_poker_cfr_plus_ev() {
+    let config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(3000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.003,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+}
+
+#[test]
+fn test_kuhn_poker_discounted_cfr_ev() {
+    let config = CfrConfig::new_with_variant(CfrVariant::new_discounted_default());
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(3000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.003,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+}
+
+#[test]
+fn test_kuhn_poker_full_tree_ev() {
+    let mut config = CfrConfig::new_default();
+    config.method = TrainingMethod::Full;
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(1000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.0005,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+
+    let exploitability = statistics.exploitability_mbb();
+    debug_assert!(exploitability < 10.0, "Exploitability: {:.4} mbb/hand", exploitability);
+}
+
+#[test]
+fn test_full_tree_training_is_reproducible() {
+    let mut config = CfrConfig::new_default();
+    config.method = TrainingMethod::Full;
+
+    let mut cfr1 = CFR::new_with_config(Kuhn::new(), config.clone());
+    let mut cfr2 = CFR::new_with_config(Kuhn::new(), config);
+
+    assert_eq!(cfr1.train_for_iters(100), cfr2.train_for_iters(100));
+    assert_eq!(
+        cfr1.build_statistics().node_util(&InfoState::new_empty()),
+        cfr2.build_statistics().node_util(&InfoState::new_empty()));
+}
+
+#[test]
+fn test_kuhn_poker_multithreaded_full_tree_ev() {
+    let mut config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    config.method = TrainingMethod::Full;
+    config.threads = 4;
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(1000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.001,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+    assert!(statistics.exploitability_mbb() < 10.0);
+}
+
+#[test]
+fn test_multithreaded_training_is_deterministic() {
+    for method in [TrainingMethod::Full, TrainingMethod::MonteCarlo, TrainingMethod::ExternalSampling] {
+        let mut config = CfrConfig::new_with_variant(CfrVariant::new_discounted_default());
+        config.method = method;
+        config.threads = 3;
+        config.seed = 7;
+
+        let mut cfr1 = CFR::new_with_config(Kuhn::new(), config.clone());
+        let mut cfr2 = CFR::new_with_config(Kuhn::new(), config);
+
+        assert_eq!(cfr1.train_for_iters(200), cfr2.train_for_iters(200));
+        assert_eq!(cfr1.tree().regrets, cfr2.tree().regrets);
+        assert_eq!(cfr1.tree().strategy_sum, cfr2.tree().strategy_sum);
+    }
+}
+
+// Regrets and strategy sums of Kuhn after full tree iterations
+fn full_tree_tables(variant: CfrVariant, threads: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut config = CfrConfig::new_with_variant(variant);
+    config.method = TrainingMethod::Full;
+    config.threads = threads;
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(100);
+
+    (cfr.tree().regrets.clone(), cfr.tree().strategy_sum.clone())
+}
+
+// Threads only change the order in which updates are summed
+fn assert_tables_close(a: &(Vec<f64>, Vec<f64>), b: &(Vec<f64>, Vec<f64>)) {
+    for (x, y) in a.0.iter().chain(a.1.iter()).zip(b.0.iter().chain(b.1.iter())) {
+        assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+    }
+}
+
+#[test]
+fn test_full_tree_does_not_depend_on_threads() {
+    for variant in [CfrVariant::Vanilla, CfrVariant::Plus] {
+        assert_tables_close(&full_tree_tables(variant, 1), &full_tree_tables(variant, 4));
+    }
+}
+
+#[test]
+fn test_discounted_full_tree_does_not_depend_on_threads() {
+    let variant = CfrVariant::new_discounted_default();
+    assert_tables_close(&full_tree_tables(variant, 1), &full_tree_tables(variant, 4));
+}
+
+#[test]
+fn test_warm_start_from_saved_tree() {
+    let path = std::env::temp_dir().join("rustysolver_test_warm_start_from_saved_tree.bin");
+    let mut config = CfrConfig::new_default();
+    config.method = TrainingMethod::Full;
+
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config.clone());
+    cfr.train_for_iters(100);
+    cfr.tree().save(&path).unwrap();
+    cfr.train_for_iters(100);
+
+    let tree = GameTree::load(Kuhn::new(), &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut warm_started = CFR::new_with_tree(tree, config);
+    warm_started.train_for_iters(100);
+
+    assert_eq!(cfr.tree().strategy_sum, warm_started.tree().strategy_sum);
+}
+
+#[test]
+fn test_resume_from_checkpoint_is_identical() {
+    let path = std::env::temp_dir().join("rustysolver_test_resume_from_checkpoint_is_identical.bin");
+    let mut config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    config.seed = 42;
+
+    let mut uninterrupted = CFR::new_with_config(Leduc::new(), config.clone());
+    uninterrupted.train_for_iters(300);
+
+    let mut checkpointed_config = config.clone();
+    checkpointed_config.checkpoint = Some(CheckpointConfig::new(path.clone(), CheckpointInterval::Iterations(100)));
+    let mut interrupted = CFR::new_with_config(Leduc::new(), checkpointed_config);
+    interrupted.train_for_iters(150);
+
+    let mut resumed = CFR::new_from_checkpoint(Leduc::new(), config, &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(resumed.iteration(), 100);
+    resumed.train_for_iters(200);
+
+    assert_eq!(uninterrupted.tree().regrets, resumed.tree().regrets);
+    assert_eq!(uninterrupted.tree().strategy_sum, resumed.tree().strategy_sum);
+}
+
+#[test]
+fn test_resume_rejects_other_variant() {
+    let path = std::env::temp_dir().join("rustysolver_test_resume_rejects_other_variant.bin");
+    let cfr = CFR::new(Kuhn::new());
+    cfr.save_checkpoint(&path).unwrap();
+
+    let result = CFR::new_from_checkpoint(Kuhn::new(), CfrConfig::new_with_variant(CfrVariant::Plus), &path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_load_rejects_other_postflop_config() {
+    let path = std::env::temp_dir().join("rustysolver_test_load_rejects_other_postflop_config.bin");
+    GameTree::new(PostflopHoldem::new(PostflopHoldemConfig::new_default())).save(&path).unwrap();
+
+    let mut config = PostflopHoldemConfig::new_default();
+    config.effective_stack = 100.0;
+    let result = GameTree::load(PostflopHoldem::new(config), &path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.err().unwrap().to_string().contains("Game config mismatch"));
+}
+
+#[test]
+fn test_kuhn_poker_external_sampling_ev() {
+    let mut config = CfrConfig::new_default();
+    config.method = TrainingMethod::ExternalSampling;
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(12000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.001,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+}
+
+#[test]
+fn test_kuhn_poker_outcome_sampling_ev() {
+    let mut config = CfrConfig::new_default();
+    config.method = TrainingMethod::new_outcome_sampling_default();
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(12000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.003,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+}
+
+#[test]
+fn test_kuhn_poker_range_vs_range_ev() {
+    let mut config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    config.method = TrainingMethod::RangeVsRange;
+    let mut cfr = CFR::new_with_config(Kuhn::new(), config);
+    cfr.train_for_iters(1000);
+    let ideal_ev = -1.0/18.0;
+
+    let statistics = cfr.build_statistics();
+    let strategy_ev = statistics.node_util(&InfoState::new_empty());
+    let strategy_ev_diff = (strategy_ev - ideal_ev).abs();
+
+    debug_assert!(strategy_ev_diff < 0.0001,
+        "Strategy EV: {:.4}, Ideal: {:.4}, Diff: {:.4}",
+        strategy_ev, ideal_ev, strategy_ev_diff);
+
+    let exploitability = statistics.exploitability_mbb();
+    debug_assert!(exploitability < 1.0, "Exploitability: {:.4} mbb/hand", exploitability);
+}
+
 // #[test]
 // fn test_leduc_poker_ev() {
 //     let mut cfr = CFR::new(Leduc::new());
@@ -54,4 +321,87 @@ fn test_kuhn_poker_ev() {
 fn test_postflop_holdem_ev() {
     let mut cfr = CFR::new(PostflopHoldem::new(PostflopHoldemConfig::new_default()));
     let ev = cfr.train_for_iters(1);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_postflop_holdem_range_vs_range_ev() {
+    let mut config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    config.method = TrainingMethod::RangeVsRange;
+    // Checks down the turn and river to keep the three street tree small
+    let mut holdem_config = PostflopHoldemConfig::new_default();
+    holdem_config.flop_raise_sizes = vec![];
+    holdem_config.turn_sizes = vec![];
+    holdem_config.river_sizes = vec![];
+    let max_contribution = holdem_config.initial_pot / 2.0 + holdem_config.effective_stack;
+    let mut cfr = CFR::new_with_config(PostflopHoldem::new(holdem_config), config);
+    let ev = cfr.train_for_iters(100);
+
+    // Nobody can win more than the opponent's stack
+    assert!(ev.abs() < max_contribution, "EV: {:.4}", ev);
+
+    let exploitability = cfr.build_statistics().exploitability_mbb();
+    debug_assert!(exploitability < 500.0, "Exploitability: {:.4} mbb/hand", exploitability);
+}
+
+fn solve_limit_holdem(board: &str, ip_range: &str, oop_range: &str, iterations: usize) -> CFR<LimitHoldem> {
+    let mut holdem_config = LimitHoldemConfig::new_default();
+    holdem_config.board = Board::new_from_string(board);
+    holdem_config.player_range.insert(Player::IP, Range::new_from_string(ip_range));
+    holdem_config.player_range.insert(Player::OOP, Range::new_from_string(oop_range));
+
+    let mut config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    config.method = TrainingMethod::RangeVsRange;
+    let mut cfr = CFR::new_with_config(LimitHoldem::new(holdem_config), config);
+    cfr.train_for_iters(iterations);
+
+    cfr
+}
+
+#[test]
+fn test_limit_holdem_locked_pot() {
+    // The royal flush cannot lose on any river, OOP gives up its half of the pot
+    let cfr = solve_limit_holdem("AhKhQhJh", "Th9c", "3c2d", 200);
+    let strategy_ev = cfr.build_statistics().node_util(&InfoState::new_empty());
+
+    assert!((strategy_ev + 6.0).abs() < 0.05, "Strategy EV: {:.4}", strategy_ev);
+}
+
+#[test]
+fn test_limit_holdem_split_pot() {
+    // Both players play the royal flush on the board, only folds lose chips
+    let cfr = solve_limit_holdem("AhKhQhJhTh", "3c2d", "5c4d", 200);
+    let strategy_ev = cfr.build_statistics().node_util(&InfoState::new_empty());
+
+    assert!(strategy_ev.abs() < 0.05, "Strategy EV: {:.4}", strategy_ev);
+}
+
+#[test]
+fn test_limit_holdem_clairvoyance_game() {
+    // The clairvoyance game of Chen and Ankenman, The Mathematics of Poker
+    // (2006): OOP holds the nuts or air as often, IP a bluff catcher, and
+    // only OOP can bet. With a pot sized bet OOP bluffs half its air, IP
+    // calls half the time, and OOP wins a quarter of the pot over its share.
+    let mut holdem_config = LimitHoldemConfig::new_default();
+    holdem_config.board = Board::new_from_string("Ks9d5c3s2h");
+    holdem_config.player_range.insert(Player::IP, Range::new_from_string("QhQc"));
+    holdem_config.player_range.insert(Player::OOP, Range::new_from_string("KdKh;7c6d"));
+    holdem_config.initial_pot = 4.0;
+    holdem_config.big_bet = 4;
+    holdem_config.cap = 1;
+
+    let mut config = CfrConfig::new_with_variant(CfrVariant::Plus);
+    config.method = TrainingMethod::RangeVsRange;
+    let mut cfr = CFR::new_with_config(LimitHoldem::new(holdem_config), config);
+    cfr.train_for_iters(2000);
+    let strategy_ev = cfr.build_statistics().node_util(&InfoState::new_empty());
+
+    assert!((strategy_ev - 1.0).abs() < 0.02, "Strategy EV: {:.4}", strategy_ev);
+}
+
+#[test]
+fn test_limit_holdem_turn_exploitability() {
+    let cfr = solve_limit_holdem("AdJc2h7s", "AA,T9s", "KK,77", 100);
+
+    let exploitability = cfr.build_statistics().exploitability_mbb();
+    assert!(exploitability < 100.0, "Exploitability: {:.4} mbb/hand", exploitability);
+}
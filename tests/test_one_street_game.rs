@@ -6,7 +6,7 @@ use rustysolver::deck::Deck;
 use rustysolver::deal::Deal;
 use rustysolver::hole_cards::HoleCards;
 use rustysolver::player_cards::PlayerCards;
-use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 use rustysolver::action::Action;
 use rustysolver::bet::Bet;
 use rustysolver::card::Card;
@@ -95,7 +95,7 @@ impl Game for OneStreetGame {
         deals
     }
 
-    fn deal(&self, rng: &mut StdRng) -> Deal {
+    fn deal(&self, rng: &mut ChaCha12Rng) -> Deal {
         Deal::new_default()
     }
 